@@ -1,7 +1,43 @@
+#![allow(clippy::too_many_arguments)] // Anchor instruction handlers take their args positionally
+
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::compute_units::sol_remaining_compute_units;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::incinerator;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::system_program;
 
 declare_id!("CafKDt5dyrYHFC2KUaJU2ux6AXEc2oFAjtdUoNaktwVX"); // Will be updated after first build
 
+/// threat-intelligence program, whose threat counter PDA signs confirmation rewards
+pub const THREAT_INTELLIGENCE_PROGRAM_ID: Pubkey =
+    pubkey!("Fo9Nm41pvuUCT2sPPsuo1XyWCQCTKf6GNwASQ1ZMEfpv");
+
+/// Max compute units an outcome callback may consume before the resolution is reverted
+pub const CALLBACK_COMPUTE_LIMIT: u64 = 50_000;
+
+/// Coverage bonus for a participant covering its agent type's specialty
+pub const SPECIALIZATION_BONUS: u8 = 10;
+
+/// Cap on the capability-count multiplier applied to reputation penalties
+pub const MAX_PENALTY_CAPABILITY_MULTIPLIER: u8 = 3;
+
+/// Byte offsets of threat-intelligence ThreatCounter's live-threat aggregates,
+/// read by `compute_crisis_index` without depending on that crate
+pub const THREAT_COUNTER_ACTIVE_THREATS_OFFSET: usize = 83;
+pub const THREAT_COUNTER_ACTIVE_SEVERITY_OFFSET: usize = 91;
+pub const THREAT_COUNTER_MIN_LEN: usize = 99;
+
+/// Live threats at which threat pressure reaches their mean severity
+pub const CRISIS_SATURATION_THREATS: u64 = 10;
+
+/// Most agents a single `batch_heartbeat` may refresh
+pub const MAX_HEARTBEAT_BATCH: usize = 20;
+
+/// Largest result artifact `verify_coordination_result` will hash (fits in one transaction)
+pub const MAX_RESULT_DATA_LEN: usize = 900;
+
 #[program]
 pub mod agent_coordinator {
     use super::*;
@@ -12,9 +48,24 @@ pub mod agent_coordinator {
         swarm.authority = ctx.accounts.authority.key();
         swarm.total_agents = 0;
         swarm.active_coordinations = 0;
+        swarm.active_low = 0;
+        swarm.active_medium = 0;
+        swarm.active_high = 0;
+        swarm.active_critical = 0;
+        swarm.total_response_latency = 0;
+        swarm.executed_count = 0;
+        swarm.type_counts = [0; 10];
         swarm.total_coordinations = 0;
+        swarm.capability_prerequisites = [0; 10];
+        swarm.pending_authority = None;
+        swarm.paused = false;
+        swarm.forfeited_bonds = 0;
         swarm.bump = ctx.bumps.swarm_registry;
 
+        let config = &mut ctx.accounts.swarm_config;
+        config.params = SwarmParams::default();
+        config.bump = ctx.bumps.swarm_config;
+
         msg!("Swarm registry initialized");
         Ok(())
     }
@@ -24,72 +75,173 @@ pub mod agent_coordinator {
         ctx: Context<RegisterAgent>,
         agent_type: AgentType,
         capabilities: Vec<Capability>,
+        stake_amount: u64,
     ) -> Result<()> {
-        require!(capabilities.len() <= 10, ErrorCode::TooManyCapabilities);
-
-        let agent = &mut ctx.accounts.agent_registration;
-        let swarm = &mut ctx.accounts.swarm_registry;
-        let clock = Clock::get()?;
-
-        agent.agent_id = ctx.accounts.agent_authority.key();
-        agent.agent_type = agent_type;
-        agent.capabilities = capabilities.clone();
-        agent.registered_at = clock.unix_timestamp;
-        agent.last_active = clock.unix_timestamp;
-        agent.active = true;
-        agent.total_actions = 0;
-        agent.successful_actions = 0;
-        agent.reputation_score = 100; // Start at 100
-        agent.bump = ctx.bumps.agent_registration;
-
-        swarm.total_agents += 1;
-
-        emit!(AgentRegistered {
-            agent_id: agent.agent_id,
+        let config = load_swarm_config(&ctx.accounts.swarm_config)?;
+        process_register_agent(
+            ctx,
             agent_type,
             capabilities,
-            timestamp: clock.unix_timestamp,
-        });
+            config.params.probation_reputation,
+            stake_amount,
+        )
+    }
 
-        msg!("Registered {:?} agent: {}", agent_type, agent.agent_id);
-        Ok(())
+    /// Register an agent with a starting reputation chosen by the swarm authority
+    /// (e.g. migrated agents with known history, or probationary newcomers)
+    pub fn register_agent_with_reputation(
+        ctx: Context<RegisterAgent>,
+        agent_type: AgentType,
+        capabilities: Vec<Capability>,
+        initial_reputation: u8,
+        stake_amount: u64,
+    ) -> Result<()> {
+        require!(initial_reputation <= 100, ErrorCode::InvalidReputation);
+
+        let swarm = load_swarm_registry(&ctx.accounts.swarm_registry)?;
+        require_keys_eq!(
+            ctx.accounts.authority.key(),
+            swarm.authority,
+            ErrorCode::Unauthorized
+        );
+
+        process_register_agent(ctx, agent_type, capabilities, initial_reputation, stake_amount)
     }
 
     /// Initiate a coordinated response to a threat
+    /// Passing `urgency: None` derives it from the threat's severity; the
+    /// threat-intelligence Threat account is then expected in `threat`.
+    /// When present, the threat's severity also feeds the priority score
     pub fn initiate_coordination(
         ctx: Context<InitiateCoordination>,
         threat_id: u64,
         required_capabilities: Vec<Capability>,
         action_plan: String,
-        urgency: Urgency,
+        urgency: Option<Urgency>,
+        expiry_seconds: i64,
+        callback_program: Option<Pubkey>,
+        min_participants: u8,
+        max_participants: u8,
+        idempotency_key: [u8; 16],
+        require_full_capability_coverage: bool,
     ) -> Result<()> {
+        require!(!ctx.accounts.swarm_registry.paused, ErrorCode::Paused);
+
+        let severity = ctx
+            .accounts
+            .threat
+            .as_ref()
+            .map(|threat| read_threat_severity(threat, threat_id))
+            .transpose()?;
+        let urgency_derived = urgency.is_none();
+        let urgency = match urgency {
+            Some(urgency) => urgency,
+            None => urgency_from_severity(severity.ok_or(ErrorCode::ThreatAccountMissing)?),
+        };
+        let priority_score = priority_score(urgency, severity.unwrap_or(0));
+
+        // A retry with the same key finds the receipt already filled in
+        let receipt = &mut ctx.accounts.initiation_receipt;
+        require!(
+            receipt.initiator == Pubkey::default(),
+            ErrorCode::DuplicateInitiation
+        );
+
+        let params = &ctx.accounts.swarm_config.params;
+        require!(
+            (params.min_expiry_seconds..=params.max_expiry_seconds).contains(&expiry_seconds),
+            ErrorCode::InvalidExpiry
+        );
+        require!(
+            (params.min_quorum..=10).contains(&min_participants),
+            ErrorCode::InvalidQuorum
+        );
+        // Capped by the participating_agents allocation
+        require!(
+            (min_participants..=10).contains(&max_participants),
+            ErrorCode::InvalidParticipantCap
+        );
+
+        // The bond is held in the coordination PDA on top of its rent, making spam costly
+        let bond = params.coordination_bond;
+        if bond > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: ctx.accounts.coordination.to_account_info(),
+                    },
+                ),
+                bond,
+            )?;
+        }
+
         let coordination = &mut ctx.accounts.coordination;
         let swarm = &mut ctx.accounts.swarm_registry;
         let clock = Clock::get()?;
 
         coordination.coordination_id = swarm.total_coordinations;
         coordination.threat_id = threat_id;
+        coordination.related_threat_ids = vec![];
         coordination.initiator = ctx.accounts.authority.key();
         coordination.required_capabilities = required_capabilities;
         coordination.action_plan = action_plan;
+        coordination.amendment_count = 0;
         coordination.urgency = urgency;
+        coordination.priority_score = priority_score;
         coordination.status = CoordinationStatus::Pending;
         coordination.participating_agents = vec![];
+        coordination.contributed_capabilities = vec![];
+        coordination.join_weights = vec![];
+        coordination.require_full_capability_coverage = require_full_capability_coverage;
+        coordination.coordination_coverage_score = 0;
+        coordination.response_latency_seconds = 0;
+        coordination.min_participants = min_participants;
+        coordination.max_participants = max_participants;
+        coordination.voters = vec![];
+        coordination.vote_weights = vec![];
+        coordination.vote_record = vec![];
         coordination.votes_for = 0;
         coordination.votes_against = 0;
+        coordination.weighted_for = 0;
+        coordination.weighted_against = 0;
         coordination.initiated_at = clock.unix_timestamp;
+        coordination.deadline = clock.unix_timestamp + expiry_seconds;
         coordination.executed_at = None;
         coordination.result_hash = None;
+        coordination.comment_count = 0;
+        coordination.cancel_reason = String::new();
+        coordination.failure_reason = String::new();
+        coordination.callback = callback_program.map(|target_program| CoordinationCallback {
+            target_program,
+            coordination_id: coordination.coordination_id,
+        });
+        coordination.callback_invoked = false;
+        coordination.total_reputation_weight = 0;
+        coordination.executed_by = Pubkey::default();
+        coordination.outcome = None;
+        coordination.rewarded_agents = vec![];
+        coordination.observers = vec![];
+        coordination.bond = bond;
         coordination.bump = ctx.bumps.coordination;
 
+        receipt.initiator = ctx.accounts.authority.key();
+        receipt.idempotency_key = idempotency_key;
+        receipt.coordination_id = coordination.coordination_id;
+        receipt.bump = ctx.bumps.initiation_receipt;
+
         swarm.total_coordinations += 1;
         swarm.active_coordinations += 1;
+        *active_for_urgency(swarm, urgency) += 1;
 
         emit!(CoordinationInitiated {
             coordination_id: coordination.coordination_id,
             threat_id,
             initiator: ctx.accounts.authority.key(),
             urgency,
+            urgency_derived,
+            priority_score,
             timestamp: clock.unix_timestamp,
         });
 
@@ -103,26 +255,58 @@ pub mod agent_coordinator {
 
     /// Agent joins a coordination
     pub fn join_coordination(ctx: Context<JoinCoordination>) -> Result<()> {
+        require!(!ctx.accounts.swarm_registry.paused, ErrorCode::Paused);
+
         let coordination = &mut ctx.accounts.coordination;
-        let agent = &ctx.accounts.agent_registration;
+        let agent = &mut ctx.accounts.agent_registration;
 
-        // Check if agent has required capabilities
-        let has_required = coordination
-            .required_capabilities
-            .iter()
-            .any(|req| agent.capabilities.contains(req));
+        require!(agent.active, ErrorCode::AgentInactive);
+
+        // Check if agent has required capabilities, recording the one it covers,
+        // preferring its specialty
+        let matching = || {
+            coordination
+                .required_capabilities
+                .iter()
+                .filter(|req| agent.capabilities.contains(req))
+        };
+        let contributed = *matching()
+            .find(|req| specialization_bonus(agent.agent_type, **req) > 0)
+            .or_else(|| matching().next())
+            .ok_or(ErrorCode::MissingCapabilities)?;
+        let bonus = specialization_bonus(agent.agent_type, contributed);
+
+        // High-stakes responses are reserved for trusted agents
+        require!(
+            reputation_tier(agent.reputation_score) >= min_tier_for(coordination.urgency),
+            ErrorCode::ReputationTooLow
+        );
 
-        require!(has_required, ErrorCode::MissingCapabilities);
         require!(
             !coordination.participating_agents.contains(&agent.agent_id),
             ErrorCode::AlreadyJoined
         );
+        require!(
+            coordination.participating_agents.len() < coordination.max_participants as usize,
+            ErrorCode::CoordinationFull
+        );
+        // Consensus counts against the participant set, so it freezes at the first vote
+        require!(coordination.voters.is_empty(), ErrorCode::VotingStarted);
 
         coordination.participating_agents.push(agent.agent_id);
+        coordination.contributed_capabilities.push(contributed);
+        // The join-time reputation is both this agent's share of the total
+        // weight and its vote weight, so the two can't drift apart
+        coordination.join_weights.push(agent.reputation_score);
+        coordination.coordination_coverage_score += bonus as u64;
+        coordination.total_reputation_weight += agent.reputation_score as u64;
+        agent.coordinations_joined += 1;
 
         emit!(AgentJoinedCoordination {
             coordination_id: coordination.coordination_id,
             agent_id: agent.agent_id,
+            contributed_capability: contributed,
+            coordination_coverage_score: coordination.coordination_coverage_score,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
@@ -135,82 +319,142 @@ pub mod agent_coordinator {
     }
 
     /// Vote on a coordination action
-    pub fn vote_on_coordination(
-        ctx: Context<VoteOnCoordination>,
+    /// If a callback is registered, its program is expected as the first remaining account
+    pub fn vote_on_coordination<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VoteOnCoordination<'info>>,
         vote: bool, // true = approve, false = reject
     ) -> Result<()> {
+        require!(!ctx.accounts.swarm_registry.paused, ErrorCode::Paused);
+
         let coordination = &mut ctx.accounts.coordination;
+        let swarm = &mut ctx.accounts.swarm_registry;
         let agent = &ctx.accounts.agent_registration;
 
+        require!(agent.active, ErrorCode::AgentInactive);
+
         // Must be a participant
+        let position = coordination
+            .participating_agents
+            .iter()
+            .position(|id| *id == agent.agent_id)
+            .ok_or(ErrorCode::NotParticipant)?;
+
+        // One vote per agent
         require!(
-            coordination.participating_agents.contains(&agent.agent_id),
-            ErrorCode::NotParticipant
+            !coordination.voters.contains(&agent.agent_id),
+            ErrorCode::AlreadyVoted
         );
 
-        if vote {
-            coordination.votes_for += 1;
-        } else {
-            coordination.votes_against += 1;
-        }
+        tally_vote(coordination, position, vote)?;
+
+        emit!(VoteCast {
+            coordination_id: coordination.coordination_id,
+            agent: agent.agent_id,
+            approve: vote,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        // Check if consensus reached (>50% of participants)
-        let total_votes = coordination.votes_for + coordination.votes_against;
-        let participant_count = coordination.participating_agents.len() as u8;
-        
-        if total_votes >= participant_count {
-            if coordination.votes_for > coordination.votes_against {
+        // Consensus is reached once every participant has voted, and approval
+        // needs a majority of the participants' combined reputation
+        // Compare in usize so a large participant set can't truncate and resolve early
+        let total_votes =
+            coordination.votes_for as usize + coordination.votes_against as usize;
+        let participant_count = coordination.participating_agents.len();
+        let quorum_met = participant_count >= coordination.min_participants as usize;
+
+        // Without quorum the coordination stays Pending even if everyone has voted
+        if quorum_met && total_votes >= participant_count {
+            if weighted_majority(coordination.weighted_for, coordination.total_reputation_weight) {
                 coordination.status = CoordinationStatus::Approved;
                 emit!(CoordinationApproved {
                     coordination_id: coordination.coordination_id,
                     votes_for: coordination.votes_for,
                     votes_against: coordination.votes_against,
+                    weighted_for: coordination.weighted_for,
+                    weighted_against: coordination.weighted_against,
                     timestamp: Clock::get()?.unix_timestamp,
                 });
             } else {
                 coordination.status = CoordinationStatus::Rejected;
+                // A rejected coordination is resolved and no longer active
+                swarm.active_coordinations = swarm.active_coordinations.saturating_sub(1);
+                release_active_urgency(swarm, coordination.urgency);
                 emit!(CoordinationRejected {
                     coordination_id: coordination.coordination_id,
                     votes_for: coordination.votes_for,
                     votes_against: coordination.votes_against,
+                    weighted_for: coordination.weighted_for,
+                    weighted_against: coordination.weighted_against,
                     timestamp: Clock::get()?.unix_timestamp,
                 });
             }
+
+            if coordination.callback.is_some() {
+                invoke_outcome_callback(coordination, ctx.remaining_accounts)?;
+            }
         }
 
         Ok(())
     }
 
-    /// Execute an approved coordination
+    /// Execute an approved coordination, recording its outcome
+    /// A Failed outcome moves the coordination to Failed rather than Executed
     pub fn execute_coordination(
         ctx: Context<ExecuteCoordination>,
         result_hash: [u8; 32],
+        outcome: ExecutionOutcome,
     ) -> Result<()> {
         let coordination = &mut ctx.accounts.coordination;
         let swarm = &mut ctx.accounts.swarm_registry;
+        ensure_executable(coordination, &ctx.accounts.authority.key())?;
         let clock = Clock::get()?;
 
-        require!(
-            coordination.status == CoordinationStatus::Approved,
-            ErrorCode::NotApproved
-        );
-
-        coordination.status = CoordinationStatus::Executed;
+        coordination.status = if outcome == ExecutionOutcome::Failed {
+            CoordinationStatus::Failed
+        } else {
+            CoordinationStatus::Executed
+        };
         coordination.executed_at = Some(clock.unix_timestamp);
         coordination.result_hash = Some(result_hash);
+        coordination.executed_by = ctx.accounts.authority.key();
+        coordination.outcome = Some(outcome);
+        coordination.response_latency_seconds = clock.unix_timestamp - coordination.initiated_at;
+
+        swarm.total_response_latency += coordination.response_latency_seconds.max(0) as u64;
+        swarm.executed_count += 1;
+        ctx.accounts.agent_registration.coordinations_executed += 1;
 
         swarm.active_coordinations = swarm.active_coordinations.saturating_sub(1);
+        release_active_urgency(swarm, coordination.urgency);
 
         emit!(CoordinationExecuted {
             coordination_id: coordination.coordination_id,
             threat_id: coordination.threat_id,
             result_hash,
+            executed_by: coordination.executed_by,
+            outcome,
+            response_latency_seconds: coordination.response_latency_seconds,
             timestamp: clock.unix_timestamp,
         });
 
+        // Close the loop on the linked threat when the swarm opted in
+        if outcome == ExecutionOutcome::FullSuccess
+            && ctx.accounts.swarm_config.params.auto_neutralize_threats
+        {
+            let (Some(threat), Some(threat_counter), Some(threat_program)) = (
+                &ctx.accounts.threat,
+                &ctx.accounts.threat_counter,
+                &ctx.accounts.threat_program,
+            ) else {
+                return err!(ErrorCode::ThreatAccountMissing);
+            };
+            neutralize_linked_threat(coordination, swarm, threat, threat_counter, threat_program)?;
+        }
+
         msg!(
-            "Coordination #{} executed successfully",
-            coordination.coordination_id
+            "Coordination #{} executed with outcome {:?}",
+            coordination.coordination_id,
+            outcome
         );
         Ok(())
     }
@@ -222,21 +466,30 @@ pub mod agent_coordinator {
         Ok(())
     }
 
-    /// Update agent reputation based on action outcome
+    /// Update agent reputation based on action outcome (swarm authority only)
     pub fn update_reputation(
         ctx: Context<UpdateReputation>,
         success: bool,
     ) -> Result<()> {
         let agent = &mut ctx.accounts.agent_registration;
+        let params = &ctx.accounts.swarm_config.params;
 
         agent.total_actions += 1;
         if success {
             agent.successful_actions += 1;
             // Increase reputation (max 100)
-            agent.reputation_score = std::cmp::min(100, agent.reputation_score + 1);
+            agent.reputation_score = std::cmp::min(
+                100,
+                agent.reputation_score.saturating_add(params.reputation_reward),
+            );
         } else {
-            // Decrease reputation (min 0)
-            agent.reputation_score = agent.reputation_score.saturating_sub(5);
+            // Decrease reputation (min 0); generalists optionally lose more
+            let penalty = if params.scale_penalty_by_capabilities {
+                capability_scaled_penalty(params.reputation_penalty, agent.capabilities.len())
+            } else {
+                params.reputation_penalty
+            };
+            agent.reputation_score = agent.reputation_score.saturating_sub(penalty);
         }
 
         emit!(ReputationUpdated {
@@ -248,167 +501,2204 @@ pub mod agent_coordinator {
 
         Ok(())
     }
-}
-
-// ============== ACCOUNTS ==============
 
-#[derive(Accounts)]
-pub struct InitializeSwarm<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + SwarmRegistry::INIT_SPACE,
-        seeds = [b"swarm"],
-        bump
-    )]
-    pub swarm_registry: Account<'info, SwarmRegistry>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    /// Participant adds a comment to a coordination's discussion thread
+    pub fn add_coordination_comment(
+        ctx: Context<AddCoordinationComment>,
+        text: String,
+    ) -> Result<()> {
+        require!(text.len() <= 500, ErrorCode::CommentTooLong);
 
-#[derive(Accounts)]
-pub struct RegisterAgent<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + AgentRegistration::INIT_SPACE,
-        seeds = [b"agent", agent_authority.key().as_ref()],
-        bump
-    )]
-    pub agent_registration: Account<'info, AgentRegistration>,
-    
-    #[account(mut, seeds = [b"swarm"], bump = swarm_registry.bump)]
-    pub swarm_registry: Account<'info, SwarmRegistry>,
-    
-    /// CHECK: Agent's signing authority
-    pub agent_authority: UncheckedAccount<'info>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+        let coordination = &mut ctx.accounts.coordination;
+        let agent = &ctx.accounts.agent_registration;
+        let comment = &mut ctx.accounts.comment;
+        let clock = Clock::get()?;
 
-#[derive(Accounts)]
-#[instruction(threat_id: u64)]
-pub struct InitiateCoordination<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + Coordination::INIT_SPACE,
-        seeds = [b"coordination", swarm_registry.total_coordinations.to_le_bytes().as_ref()],
-        bump
-    )]
-    pub coordination: Account<'info, Coordination>,
-    
-    #[account(mut, seeds = [b"swarm"], bump = swarm_registry.bump)]
-    pub swarm_registry: Account<'info, SwarmRegistry>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+        // Discussion closes once consensus has been reached
+        require!(
+            coordination.status == CoordinationStatus::Pending,
+            ErrorCode::CoordinationResolved
+        );
+        require!(
+            coordination.participating_agents.contains(&agent.agent_id),
+            ErrorCode::NotParticipant
+        );
 
-#[derive(Accounts)]
-pub struct JoinCoordination<'info> {
-    #[account(mut)]
-    pub coordination: Account<'info, Coordination>,
-    
-    pub agent_registration: Account<'info, AgentRegistration>,
-    
-    pub authority: Signer<'info>,
-}
+        comment.coordination_id = coordination.coordination_id;
+        comment.index = coordination.comment_count;
+        comment.author = agent.agent_id;
+        comment.text = text;
+        comment.created_at = clock.unix_timestamp;
+        comment.bump = ctx.bumps.comment;
 
-#[derive(Accounts)]
-pub struct VoteOnCoordination<'info> {
-    #[account(mut)]
-    pub coordination: Account<'info, Coordination>,
-    
-    pub agent_registration: Account<'info, AgentRegistration>,
-    
-    pub authority: Signer<'info>,
-}
+        coordination.comment_count += 1;
 
-#[derive(Accounts)]
-pub struct ExecuteCoordination<'info> {
-    #[account(mut)]
-    pub coordination: Account<'info, Coordination>,
-    
-    #[account(mut, seeds = [b"swarm"], bump = swarm_registry.bump)]
-    pub swarm_registry: Account<'info, SwarmRegistry>,
-    
-    pub authority: Signer<'info>,
-}
+        emit!(CoordinationCommented {
+            coordination_id: coordination.coordination_id,
+            index: comment.index,
+            author: agent.agent_id,
+            timestamp: clock.unix_timestamp,
+        });
 
-#[derive(Accounts)]
-pub struct Heartbeat<'info> {
-    #[account(mut)]
-    pub agent_registration: Account<'info, AgentRegistration>,
-    
-    pub authority: Signer<'info>,
-}
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct UpdateReputation<'info> {
-    #[account(mut)]
-    pub agent_registration: Account<'info, AgentRegistration>,
-    
-    pub authority: Signer<'info>,
-}
+    /// Apply (or claw back) the reputation bonus for confirming an escalated threat
+    /// Only callable via CPI from threat-intelligence, signed by its threat counter PDA.
+    /// Returns the change actually applied, which the cap at 100 (or 0) can shrink
+    pub fn apply_confirmation_reward(
+        ctx: Context<ApplyConfirmationReward>,
+        amount: u8,
+        clawback: bool,
+    ) -> Result<u8> {
+        let agent = &mut ctx.accounts.agent_registration;
 
-// ============== STATE ==============
+        let (new_score, applied) = reputation_delta(agent.reputation_score, amount, clawback);
+        agent.reputation_score = new_score;
 
-#[account]
-#[derive(InitSpace)]
-pub struct SwarmRegistry {
-    pub authority: Pubkey,
-    pub total_agents: u64,
-    pub active_coordinations: u64,
-    pub total_coordinations: u64,
-    pub bump: u8,
-}
+        emit!(ReputationUpdated {
+            agent_id: agent.agent_id,
+            new_score: agent.reputation_score,
+            success: !clawback,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-#[account]
-#[derive(InitSpace)]
-pub struct AgentRegistration {
-    pub agent_id: Pubkey,
-    pub agent_type: AgentType,
-    #[max_len(10)]
-    pub capabilities: Vec<Capability>,
-    pub registered_at: i64,
-    pub last_active: i64,
-    pub active: bool,
-    pub total_actions: u64,
-    pub successful_actions: u64,
-    pub reputation_score: u8, // 0-100
-    pub bump: u8,
-}
+        Ok(applied)
+    }
 
-#[account]
-#[derive(InitSpace)]
-pub struct Coordination {
-    pub coordination_id: u64,
-    pub threat_id: u64,
-    pub initiator: Pubkey,
-    #[max_len(5)]
-    pub required_capabilities: Vec<Capability>,
-    #[max_len(1000)]
-    pub action_plan: String,
-    pub urgency: Urgency,
-    pub status: CoordinationStatus,
-    #[max_len(10)]
-    pub participating_agents: Vec<Pubkey>,
-    pub votes_for: u8,
-    pub votes_against: u8,
-    pub initiated_at: i64,
-    pub executed_at: Option<i64>,
-    pub result_hash: Option<[u8; 32]>,
-    pub bump: u8,
-}
+    /// Take an agent offline (agent's own authority or swarm authority)
+    pub fn deactivate_agent(ctx: Context<DeactivateAgent>) -> Result<()> {
+        let agent = &mut ctx.accounts.agent_registration;
+        let swarm = &mut ctx.accounts.swarm_registry;
+        let signer = ctx.accounts.authority.key();
+
+        require!(
+            signer == agent.agent_id || signer == swarm.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(agent.active, ErrorCode::AgentInactive);
+
+        agent.active = false;
+        agent.deactivated_by = Some(signer);
+        swarm.total_agents = swarm.total_agents.saturating_sub(1);
+        let type_count = &mut swarm.type_counts[agent.agent_type as usize];
+        *type_count = type_count.saturating_sub(1);
+
+        emit!(AgentDeactivated {
+            agent_id: agent.agent_id,
+            deactivated_by: signer,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Deactivated agent: {}", agent.agent_id);
+        Ok(())
+    }
+
+    /// Bring a previously deactivated agent back online
+    /// An agent retired by the swarm authority can only be reactivated by it
+    pub fn reactivate_agent(ctx: Context<DeactivateAgent>) -> Result<()> {
+        let agent = &mut ctx.accounts.agent_registration;
+        let swarm = &mut ctx.accounts.swarm_registry;
+        let signer = ctx.accounts.authority.key();
+        let clock = Clock::get()?;
+
+        require!(!agent.active, ErrorCode::AgentNotDeactivated);
+        require!(
+            can_reactivate(agent, signer, swarm.authority),
+            ErrorCode::Unauthorized
+        );
+
+        agent.active = true;
+        agent.deactivated_by = None;
+        agent.last_active = clock.unix_timestamp;
+        swarm.total_agents += 1;
+        swarm.type_counts[agent.agent_type as usize] += 1;
+
+        emit!(AgentReactivated {
+            agent_id: agent.agent_id,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Reactivated agent: {}", agent.agent_id);
+        Ok(())
+    }
+
+    /// Cancel a pending coordination whose deadline has passed (callable by anyone)
+    pub fn expire_coordination(ctx: Context<ExpireCoordination>) -> Result<()> {
+        let coordination = &mut ctx.accounts.coordination;
+        let swarm = &mut ctx.accounts.swarm_registry;
+        let clock = Clock::get()?;
+
+        require!(
+            coordination.status == CoordinationStatus::Pending,
+            ErrorCode::CoordinationResolved
+        );
+        require!(
+            clock.unix_timestamp > coordination.deadline,
+            ErrorCode::NotExpired
+        );
+
+        coordination.status = CoordinationStatus::Cancelled;
+        swarm.active_coordinations = swarm.active_coordinations.saturating_sub(1);
+        release_active_urgency(swarm, coordination.urgency);
+        let (coordination_info, swarm_info) =
+            (coordination.to_account_info(), swarm.to_account_info());
+        let bond_forfeited = forfeit_bond(coordination, &coordination_info, swarm, &swarm_info)?;
+
+        emit!(CoordinationExpired {
+            coordination_id: coordination.coordination_id,
+            deadline: coordination.deadline,
+            bond_forfeited,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Coordination #{} expired", coordination.coordination_id);
+        Ok(())
+    }
+
+    /// Agent leaves a coordination it joined, before voting begins
+    pub fn leave_coordination(ctx: Context<LeaveCoordination>) -> Result<()> {
+        let coordination = &mut ctx.accounts.coordination;
+        let agent = &mut ctx.accounts.agent_registration;
+
+        require!(
+            coordination.status == CoordinationStatus::Pending,
+            ErrorCode::CoordinationLocked
+        );
+        // Tallies aren't attributed per agent, so the participant set
+        // can only shrink before anyone has voted
+        require!(
+            coordination.votes_for == 0 && coordination.votes_against == 0,
+            ErrorCode::VotingStarted
+        );
+
+        let position = coordination
+            .participating_agents
+            .iter()
+            .position(|id| *id == agent.agent_id)
+            .ok_or(ErrorCode::NotParticipant)?;
+        coordination.participating_agents.remove(position);
+        let contributed = coordination.contributed_capabilities.remove(position);
+        let join_weight = coordination.join_weights.remove(position);
+        coordination.coordination_coverage_score = coordination
+            .coordination_coverage_score
+            .saturating_sub(specialization_bonus(agent.agent_type, contributed) as u64);
+        // Subtract what was added at join, not the current reputation
+        coordination.total_reputation_weight = coordination
+            .total_reputation_weight
+            .saturating_sub(join_weight as u64);
+        agent.coordinations_joined = agent.coordinations_joined.saturating_sub(1);
+
+        emit!(AgentLeftCoordination {
+            coordination_id: coordination.coordination_id,
+            agent_id: agent.agent_id,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!(
+            "Agent {} left coordination #{}",
+            agent.agent_id,
+            coordination.coordination_id
+        );
+        Ok(())
+    }
+
+    /// Set which capabilities an agent must also declare to claim `capability`
+    pub fn set_capability_prerequisites(
+        ctx: Context<SetCapabilityPrerequisites>,
+        capability: Capability,
+        prerequisites: Vec<Capability>,
+    ) -> Result<()> {
+        let swarm = &mut ctx.accounts.swarm_registry;
+
+        swarm.capability_prerequisites[capability as usize] = capability_mask(&prerequisites);
+
+        emit!(CapabilityPrerequisitesUpdated {
+            capability,
+            prerequisites,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Replace an agent's capabilities (agent's own authority only)
+    pub fn update_capabilities(
+        ctx: Context<UpdateCapabilities>,
+        capabilities: Vec<Capability>,
+    ) -> Result<()> {
+        require!(capabilities.len() <= 10, ErrorCode::TooManyCapabilities);
+        check_capability_prerequisites(
+            &ctx.accounts.swarm_registry.capability_prerequisites,
+            &capabilities,
+        )?;
+
+        let agent = &mut ctx.accounts.agent_registration;
+        capabilities_valid_for_type(agent.agent_type, &capabilities)?;
+        let old_capabilities = std::mem::replace(&mut agent.capabilities, capabilities.clone());
+
+        emit!(CapabilitiesUpdated {
+            agent_id: agent.agent_id,
+            old_capabilities,
+            new_capabilities: capabilities,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Updated capabilities for agent {}", agent.agent_id);
+        Ok(())
+    }
+
+    /// Return a deactivated agent's stake to its authority
+    pub fn withdraw_stake(ctx: Context<WithdrawStake>) -> Result<()> {
+        let agent = &mut ctx.accounts.agent_registration;
+        let amount = agent.staked;
+
+        require!(!agent.active, ErrorCode::AgentStillActive);
+        require!(amount > 0, ErrorCode::NoStake);
+        require!(
+            agent.reputation_score >= ctx.accounts.swarm_config.params.min_withdraw_reputation,
+            ErrorCode::ReputationTooLow
+        );
+
+        agent.staked = 0;
+        agent.sub_lamports(amount)?;
+        ctx.accounts.authority.add_lamports(amount)?;
+
+        emit!(StakeWithdrawn {
+            agent_id: agent.agent_id,
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Burn part of a zero-reputation agent's stake (swarm authority only)
+    pub fn slash_stake(ctx: Context<SlashStake>) -> Result<()> {
+        let agent = &mut ctx.accounts.agent_registration;
+
+        require!(agent.reputation_score == 0, ErrorCode::ReputationNotZero);
+        require!(agent.staked > 0, ErrorCode::NoStake);
+
+        let amount =
+            slash_amount(agent.staked, ctx.accounts.swarm_config.params.stake_slash_percent);
+        agent.staked -= amount;
+        agent.sub_lamports(amount)?;
+        ctx.accounts.incinerator.add_lamports(amount)?;
+
+        emit!(StakeSlashed {
+            agent_id: agent.agent_id,
+            amount,
+            remaining: agent.staked,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Initiator aborts a pending coordination (e.g. the threat was a false alarm)
+    pub fn cancel_coordination(
+        ctx: Context<CancelCoordination>,
+        reason: String,
+    ) -> Result<()> {
+        require!(reason.len() <= 200, ErrorCode::ReasonTooLong);
+
+        let coordination = &mut ctx.accounts.coordination;
+        let swarm = &mut ctx.accounts.swarm_registry;
+
+        require!(
+            coordination.status == CoordinationStatus::Pending,
+            ErrorCode::CannotCancel
+        );
+
+        coordination.status = CoordinationStatus::Cancelled;
+        coordination.cancel_reason = reason.clone();
+        swarm.active_coordinations = swarm.active_coordinations.saturating_sub(1);
+        release_active_urgency(swarm, coordination.urgency);
+        let (coordination_info, swarm_info) =
+            (coordination.to_account_info(), swarm.to_account_info());
+        let bond_forfeited = forfeit_bond(coordination, &coordination_info, swarm, &swarm_info)?;
+
+        emit!(CoordinationCancelled {
+            coordination_id: coordination.coordination_id,
+            reason,
+            bond_forfeited,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Coordination #{} cancelled", coordination.coordination_id);
+        Ok(())
+    }
+
+    /// Record that an executed coordination didn't achieve its goal
+    /// Participants are emitted so an off-chain process can penalize them via `update_reputation`
+    pub fn mark_coordination_failed(
+        ctx: Context<MarkCoordinationFailed>,
+        failure_reason: String,
+    ) -> Result<()> {
+        require!(failure_reason.len() <= 300, ErrorCode::ReasonTooLong);
+
+        let coordination = &mut ctx.accounts.coordination;
+        let signer = ctx.accounts.authority.key();
+
+        require!(
+            signer == coordination.initiator || signer == ctx.accounts.swarm_registry.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(
+            coordination.status == CoordinationStatus::Executed,
+            ErrorCode::NotExecuted
+        );
+
+        coordination.status = CoordinationStatus::Failed;
+        coordination.failure_reason = failure_reason.clone();
+
+        emit!(CoordinationFailed {
+            coordination_id: coordination.coordination_id,
+            threat_id: coordination.threat_id,
+            failure_reason,
+            participating_agents: coordination.participating_agents.clone(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Coordination #{} marked failed", coordination.coordination_id);
+        Ok(())
+    }
+
+    /// Replace the swarm's tunables (swarm authority only)
+    pub fn update_config(ctx: Context<UpdateConfig>, params: SwarmParams) -> Result<()> {
+        require!(params.min_quorum >= 1 && params.min_quorum <= 10, ErrorCode::InvalidConfig);
+        require!(
+            params.probation_reputation <= 100
+                && params.min_withdraw_reputation <= 100
+                && params.stake_slash_percent <= 100,
+            ErrorCode::InvalidConfig
+        );
+        require!(
+            params.min_expiry_seconds > 0 && params.min_expiry_seconds <= params.max_expiry_seconds,
+            ErrorCode::InvalidConfig
+        );
+        require!(
+            params.crisis_threat_weight as u16 + params.crisis_urgency_weight as u16 == 100,
+            ErrorCode::InvalidConfig
+        );
+
+        ctx.accounts.swarm_config.params = params;
+
+        emit!(ConfigUpdated {
+            params,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Swarm config updated");
+        Ok(())
+    }
+
+    /// Propose a new swarm authority; takes effect once they accept
+    pub fn transfer_swarm_authority(
+        ctx: Context<TransferSwarmAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        let swarm = &mut ctx.accounts.swarm_registry;
+        swarm.pending_authority = Some(new_authority);
+
+        emit!(AuthorityTransferProposed {
+            current_authority: swarm.authority,
+            pending_authority: new_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Swarm authority transfer proposed to {}", new_authority);
+        Ok(())
+    }
+
+    /// Accept a pending swarm authority transfer
+    pub fn accept_swarm_authority(ctx: Context<AcceptSwarmAuthority>) -> Result<()> {
+        let swarm = &mut ctx.accounts.swarm_registry;
+        let new_authority = ctx.accounts.new_authority.key();
+
+        require!(
+            swarm.pending_authority == Some(new_authority),
+            ErrorCode::NotPendingAuthority
+        );
+
+        let old_authority = swarm.authority;
+        swarm.authority = new_authority;
+        swarm.pending_authority = None;
+
+        emit!(AuthorityTransferred {
+            old_authority,
+            new_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Swarm authority transferred to {}", new_authority);
+        Ok(())
+    }
+
+    /// Emergency circuit breaker: halts new coordinations, joins and votes.
+    /// Execution of approved coordinations and heartbeats stay available
+    pub fn set_pause(ctx: Context<SetPause>, paused: bool) -> Result<()> {
+        ctx.accounts.swarm_registry.paused = paused;
+
+        emit!(PauseStateChanged {
+            paused,
+            authority: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Swarm paused: {}", paused);
+        Ok(())
+    }
+
+    /// Read-only voting progress for a coordination
+    pub fn get_coordination_summary(
+        ctx: Context<GetCoordinationSummary>,
+    ) -> Result<CoordinationSummary> {
+        let coordination = &ctx.accounts.coordination;
+
+        let participant_count = coordination.participating_agents.len();
+        let votes_cast = coordination.voters.len();
+        let weighted_total = coordination.weighted_for + coordination.weighted_against;
+
+        // Approval is reputation-weighted, matching how votes are resolved
+        let approval_ratio = (coordination.weighted_for * 10_000)
+            .checked_div(weighted_total)
+            .unwrap_or(0) as u16;
+
+        Ok(CoordinationSummary {
+            participant_count: participant_count as u8,
+            votes_cast: votes_cast as u8,
+            votes_remaining: participant_count.saturating_sub(votes_cast) as u8,
+            approval_ratio,
+            is_quorum_met: participant_count >= coordination.min_participants as usize,
+        })
+    }
+
+    /// Read-only check of whether an agent could join a coordination:
+    /// it holds a required capability, meets the urgency's reputation floor,
+    /// isn't already participating, and voting hasn't started
+    pub fn check_agent_eligibility(ctx: Context<CheckAgentEligibility>) -> Result<bool> {
+        let coordination = &ctx.accounts.coordination;
+        let agent = &ctx.accounts.agent_registration;
+
+        // Mirrors the checks join_coordination enforces
+        let has_required = coordination
+            .required_capabilities
+            .iter()
+            .any(|req| agent.capabilities.contains(req));
+
+        Ok(has_required
+            && !ctx.accounts.swarm_registry.paused
+            && agent.active
+            && reputation_tier(agent.reputation_score) >= min_tier_for(coordination.urgency)
+            && coordination.voters.is_empty()
+            && coordination.participating_agents.len() < coordination.max_participants as usize
+            && !coordination.participating_agents.contains(&agent.agent_id))
+    }
+
+    /// Read-only liveness check: active and heard from within `max_staleness_seconds`
+    pub fn is_agent_live(ctx: Context<IsAgentLive>, max_staleness_seconds: i64) -> Result<bool> {
+        let agent = &ctx.accounts.agent_registration;
+        let now = Clock::get()?.unix_timestamp;
+
+        Ok(agent.active && now.saturating_sub(agent.last_active) <= max_staleness_seconds)
+    }
+
+    /// Active coordinations by urgency: [low, medium, high, critical]
+    pub fn get_urgency_breakdown(ctx: Context<GetUrgencyBreakdown>) -> Result<[u64; 4]> {
+        let swarm = &ctx.accounts.swarm_registry;
+        Ok([
+            swarm.active_low,
+            swarm.active_medium,
+            swarm.active_high,
+            swarm.active_critical,
+        ])
+    }
+
+    /// Swarm-wide crisis index, 0-100: a weighted blend of threat pressure (live
+    /// threats' mean severity, scaled up to CRISIS_SATURATION_THREATS of them) and
+    /// the mean urgency of active coordinations. Weights come from SwarmParams
+    pub fn compute_crisis_index(ctx: Context<ComputeCrisisIndex>) -> Result<u8> {
+        let (active_threats, active_severity_sum) =
+            read_threat_aggregates(&ctx.accounts.threat_counter)?;
+        let swarm = &ctx.accounts.swarm_registry;
+        let params = &ctx.accounts.swarm_config.params;
+
+        Ok(crisis_index(
+            threat_pressure(active_threats, active_severity_sum),
+            urgency_pressure([
+                swarm.active_low,
+                swarm.active_medium,
+                swarm.active_high,
+                swarm.active_critical,
+            ]),
+            params.crisis_threat_weight,
+            params.crisis_urgency_weight,
+        ))
+    }
+
+    /// Authorize a hot key to heartbeat, join, leave and vote on the agent's behalf
+    /// Passing the default pubkey clears the delegate
+    pub fn set_delegate(ctx: Context<SetDelegate>, delegate: Pubkey) -> Result<()> {
+        let agent = &mut ctx.accounts.agent_registration;
+        agent.delegate = (delegate != Pubkey::default()).then_some(delegate);
+
+        emit!(DelegateUpdated {
+            agent_id: agent.agent_id,
+            delegate: agent.delegate,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Agent {} delegate set to {:?}", agent.agent_id, agent.delegate);
+        Ok(())
+    }
+
+    /// Refund the initiator's bond once the coordination has been decided
+    pub fn reclaim_bond(ctx: Context<ReclaimBond>) -> Result<()> {
+        let coordination = &mut ctx.accounts.coordination;
+        let coordination_info = coordination.to_account_info();
+        let amount = refund_bond(
+            coordination,
+            &coordination_info,
+            &ctx.accounts.initiator.to_account_info(),
+        )?;
+
+        emit!(BondReclaimed {
+            coordination_id: coordination.coordination_id,
+            initiator: coordination.initiator,
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Bond reclaimed for coordination #{}", coordination.coordination_id);
+        Ok(())
+    }
+
+    /// Swarm authority withdraws the bonds forfeited by cancelled or expired
+    /// coordinations, e.g. to a treasury
+    pub fn sweep_forfeited_bonds(ctx: Context<SweepForfeitedBonds>) -> Result<()> {
+        let swarm = &mut ctx.accounts.swarm_registry;
+        let swarm_info = swarm.to_account_info();
+        let recipient = ctx.accounts.recipient.to_account_info();
+        let amount = sweep_bonds(swarm, &swarm_info, &recipient)?;
+
+        emit!(ForfeitedBondsSwept {
+            recipient: recipient.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Swept {} lamports of forfeited bonds", amount);
+        Ok(())
+    }
+
+    /// Initiator revises the action plan while it's still under discussion
+    pub fn amend_action_plan(ctx: Context<AmendActionPlan>, new_plan: String) -> Result<()> {
+        require!(new_plan.len() <= 1000, ErrorCode::ActionPlanTooLong);
+
+        let coordination = &mut ctx.accounts.coordination;
+
+        require!(
+            coordination.status == CoordinationStatus::Pending,
+            ErrorCode::CoordinationResolved
+        );
+        // Voters must be deciding on the plan they read
+        require!(coordination.voters.is_empty(), ErrorCode::PlanLocked);
+
+        coordination.amendment_count = coordination
+            .amendment_count
+            .checked_add(1)
+            .ok_or(ErrorCode::TooManyAmendments)?;
+        coordination.action_plan = new_plan;
+
+        emit!(ActionPlanAmended {
+            coordination_id: coordination.coordination_id,
+            revision: coordination.amendment_count,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!(
+            "Coordination #{} action plan amended (revision {})",
+            coordination.coordination_id,
+            coordination.amendment_count
+        );
+        Ok(())
+    }
+
+    /// Read-only view of an agent's reputation tier
+    pub fn get_agent_tier(ctx: Context<GetAgentTier>) -> Result<ReputationTier> {
+        Ok(reputation_tier(ctx.accounts.agent_registration.reputation_score))
+    }
+
+    /// Check that `result_data` hashes to the result recorded at execution
+    pub fn verify_coordination_result(
+        ctx: Context<VerifyCoordinationResult>,
+        result_data: Vec<u8>,
+    ) -> Result<bool> {
+        require!(
+            result_data.len() <= MAX_RESULT_DATA_LEN,
+            ErrorCode::ResultDataTooLarge
+        );
+
+        let coordination = &ctx.accounts.coordination;
+        let recorded = coordination.result_hash.ok_or(ErrorCode::NotExecuted)?;
+        let verified = hash(&result_data).to_bytes() == recorded;
+
+        emit!(CoordinationResultVerified {
+            coordination_id: coordination.coordination_id,
+            verifier: ctx.accounts.verifier.key(),
+            verified,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!(
+            "Coordination #{} result verification: {}",
+            coordination.coordination_id,
+            verified
+        );
+        Ok(verified)
+    }
+
+    /// Durably bar a key from registering (swarm authority only)
+    pub fn ban_agent(ctx: Context<BanAgent>, agent: Pubkey) -> Result<()> {
+        let banned = &mut ctx.accounts.banned_agent;
+        let clock = Clock::get()?;
+
+        banned.agent = agent;
+        banned.banned_by = ctx.accounts.authority.key();
+        banned.banned_at = clock.unix_timestamp;
+        banned.bump = ctx.bumps.banned_agent;
+
+        emit!(AgentBanned {
+            agent,
+            banned_by: banned.banned_by,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Banned agent: {}", agent);
+        Ok(())
+    }
+
+    /// Lift a ban, closing its record (swarm authority only)
+    pub fn unban_agent(ctx: Context<UnbanAgent>) -> Result<()> {
+        let agent = ctx.accounts.banned_agent.agent;
+
+        emit!(AgentUnbanned {
+            agent,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Unbanned agent: {}", agent);
+        Ok(())
+    }
+
+    /// Tune just the reputation gain and loss applied by `update_reputation`
+    /// (swarm authority only); they live in the swarm's tunables
+    pub fn set_reputation_params(
+        ctx: Context<UpdateConfig>,
+        reward_delta: u8,
+        penalty_delta: u8,
+    ) -> Result<()> {
+        require!(
+            reward_delta <= 100 && penalty_delta <= 100,
+            ErrorCode::InvalidConfig
+        );
+
+        let params = &mut ctx.accounts.swarm_config.params;
+        params.reputation_reward = reward_delta;
+        params.reputation_penalty = penalty_delta;
+
+        emit!(ReputationParamsUpdated {
+            reward_delta,
+            penalty_delta,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!(
+            "Reputation params set: +{} / -{}",
+            reward_delta,
+            penalty_delta
+        );
+        Ok(())
+    }
+
+    /// Refresh `last_active` for a fleet of agents in one transaction
+    /// remaining_accounts: the AgentRegistrations, each owned or delegated to the signer
+    pub fn batch_heartbeat<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchHeartbeat<'info>>,
+    ) -> Result<()> {
+        let remaining = ctx.remaining_accounts;
+        require!(
+            remaining.len() <= MAX_HEARTBEAT_BATCH,
+            ErrorCode::TooManyAgents
+        );
+
+        let signer = ctx.accounts.authority.key();
+        let now = Clock::get()?.unix_timestamp;
+        let mut agent_ids = Vec::with_capacity(remaining.len());
+
+        for info in remaining {
+            require!(info.is_writable, ErrorCode::Unauthorized);
+            let mut agent = Account::<AgentRegistration>::try_from(info)?;
+            require!(
+                agent.agent_id == signer || agent.delegate == Some(signer),
+                ErrorCode::Unauthorized
+            );
+
+            agent.last_active = now;
+            agent.exit(&crate::ID)?;
+            agent_ids.push(agent.agent_id);
+        }
+
+        emit!(BatchHeartbeatRecorded {
+            authority: signer,
+            agent_ids,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Participant claims its reputation reward for a fully successful coordination
+    pub fn claim_coordination_reward(ctx: Context<ClaimCoordinationReward>) -> Result<()> {
+        let coordination = &mut ctx.accounts.coordination;
+        let agent = &mut ctx.accounts.agent_registration;
+
+        require!(agent.active, ErrorCode::AgentInactive);
+        require!(
+            coordination.status == CoordinationStatus::Executed
+                && coordination.outcome == Some(ExecutionOutcome::FullSuccess),
+            ErrorCode::NotRewardable
+        );
+        require!(
+            coordination.participating_agents.contains(&agent.agent_id),
+            ErrorCode::NotParticipant
+        );
+        require!(
+            !coordination.rewarded_agents.contains(&agent.agent_id),
+            ErrorCode::AlreadyRewarded
+        );
+
+        coordination.rewarded_agents.push(agent.agent_id);
+        agent.total_actions += 1;
+        agent.successful_actions += 1;
+        agent.reputation_score = std::cmp::min(
+            100,
+            agent
+                .reputation_score
+                .saturating_add(ctx.accounts.swarm_config.params.reputation_reward),
+        );
+
+        emit!(CoordinationRewardClaimed {
+            coordination_id: coordination.coordination_id,
+            agent_id: agent.agent_id,
+            new_score: agent.reputation_score,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!(
+            "Agent {} claimed reward for coordination #{}",
+            agent.agent_id,
+            coordination.coordination_id
+        );
+        Ok(())
+    }
+
+    /// Active agents by type, indexed by `AgentType as usize`
+    pub fn get_type_distribution(ctx: Context<GetTypeDistribution>) -> Result<[u64; 10]> {
+        Ok(ctx.accounts.swarm_registry.type_counts)
+    }
+
+    /// Attach another threat from the same incident to a pending coordination (initiator only)
+    pub fn add_related_threat(ctx: Context<AddRelatedThreat>, threat_id: u64) -> Result<()> {
+        let coordination = &mut ctx.accounts.coordination;
+
+        require!(
+            coordination.status == CoordinationStatus::Pending,
+            ErrorCode::CoordinationResolved
+        );
+        require!(
+            threat_id != coordination.threat_id
+                && !coordination.related_threat_ids.contains(&threat_id),
+            ErrorCode::ThreatAlreadyLinked
+        );
+        require!(
+            coordination.related_threat_ids.len() < 5,
+            ErrorCode::TooManyRelatedThreats
+        );
+
+        coordination.related_threat_ids.push(threat_id);
+
+        emit!(RelatedThreatAdded {
+            coordination_id: coordination.coordination_id,
+            threat_id,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!(
+            "Threat {} linked to coordination #{}",
+            threat_id,
+            coordination.coordination_id
+        );
+        Ok(())
+    }
+
+    /// Refresh a live coordination's priority after its threat's severity changed
+    pub fn recompute_priority(ctx: Context<RecomputePriority>) -> Result<()> {
+        let coordination = &mut ctx.accounts.coordination;
+
+        require!(
+            matches!(
+                coordination.status,
+                CoordinationStatus::Pending | CoordinationStatus::Approved
+            ),
+            ErrorCode::CoordinationResolved
+        );
+
+        let severity = read_threat_severity(&ctx.accounts.threat, coordination.threat_id)?;
+        let old_score = coordination.priority_score;
+        coordination.priority_score = priority_score(coordination.urgency, severity);
+
+        emit!(PriorityRecomputed {
+            coordination_id: coordination.coordination_id,
+            old_score,
+            new_score: coordination.priority_score,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Non-participating agent records that it reviewed a coordination; observers don't vote
+    pub fn register_observer(ctx: Context<RegisterObserver>) -> Result<()> {
+        let coordination = &mut ctx.accounts.coordination;
+        let agent = &ctx.accounts.agent_registration;
+
+        require!(agent.active, ErrorCode::AgentInactive);
+        require!(
+            matches!(
+                coordination.status,
+                CoordinationStatus::Pending | CoordinationStatus::Approved
+            ),
+            ErrorCode::CoordinationResolved
+        );
+        require!(
+            !coordination.participating_agents.contains(&agent.agent_id),
+            ErrorCode::AlreadyJoined
+        );
+        require!(
+            !coordination.observers.contains(&agent.agent_id),
+            ErrorCode::AlreadyObserving
+        );
+        require!(coordination.observers.len() < 5, ErrorCode::TooManyObservers);
+
+        coordination.observers.push(agent.agent_id);
+
+        emit!(ObserverRegistered {
+            coordination_id: coordination.coordination_id,
+            agent_id: agent.agent_id,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!(
+            "Agent {} observing coordination #{}",
+            agent.agent_id,
+            coordination.coordination_id
+        );
+        Ok(())
+    }
+}
+
+// ============== ACCOUNTS ==============
+
+#[derive(Accounts)]
+pub struct InitializeSwarm<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + SwarmRegistry::INIT_SPACE,
+        seeds = [b"swarm"],
+        bump
+    )]
+    pub swarm_registry: Account<'info, SwarmRegistry>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + SwarmConfig::INIT_SPACE,
+        seeds = [b"swarm_config"],
+        bump
+    )]
+    pub swarm_config: Account<'info, SwarmConfig>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterAgent<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AgentRegistration::INIT_SPACE,
+        seeds = [b"agent", agent_authority.key().as_ref()],
+        bump
+    )]
+    pub agent_registration: Account<'info, AgentRegistration>,
+    
+    /// CHECK: Loaded via `load_swarm_registry` so a missing swarm fails with `SwarmNotInitialized`
+    #[account(mut)]
+    pub swarm_registry: UncheckedAccount<'info>,
+    
+    /// CHECK: Loaded via `load_swarm_config`, for the same reason as `swarm_registry`
+    pub swarm_config: UncheckedAccount<'info>,
+    
+    /// CHECK: Agent's signing authority
+    pub agent_authority: UncheckedAccount<'info>,
+    
+    /// CHECK: BannedAgent PDA for `agent_authority`; registration requires it to be unallocated
+    #[account(seeds = [b"banned", agent_authority.key().as_ref()], bump)]
+    pub banned_agent: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    threat_id: u64,
+    required_capabilities: Vec<Capability>,
+    action_plan: String,
+    urgency: Option<Urgency>,
+    expiry_seconds: i64,
+    callback_program: Option<Pubkey>,
+    min_participants: u8,
+    max_participants: u8,
+    idempotency_key: [u8; 16],
+    require_full_capability_coverage: bool,
+)]
+pub struct InitiateCoordination<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Coordination::INIT_SPACE,
+        seeds = [b"coordination", swarm_registry.total_coordinations.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub coordination: Account<'info, Coordination>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + InitiationReceipt::INIT_SPACE,
+        seeds = [b"initiation", authority.key().as_ref(), idempotency_key.as_ref()],
+        bump
+    )]
+    pub initiation_receipt: Account<'info, InitiationReceipt>,
+    
+    #[account(mut, seeds = [b"swarm"], bump = swarm_registry.bump)]
+    pub swarm_registry: Account<'info, SwarmRegistry>,
+    
+    #[account(seeds = [b"swarm_config"], bump = swarm_config.bump)]
+    pub swarm_config: Account<'info, SwarmConfig>,
+    
+    /// CHECK: threat-intelligence Threat, read by `read_threat_severity` for urgency and priority
+    pub threat: Option<UncheckedAccount<'info>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct JoinCoordination<'info> {
+    #[account(mut)]
+    pub coordination: Account<'info, Coordination>,
+    
+    #[account(seeds = [b"swarm"], bump = swarm_registry.bump)]
+    pub swarm_registry: Account<'info, SwarmRegistry>,
+    
+    #[account(
+        mut,
+        constraint = agent_registration.agent_id == authority.key()
+            || agent_registration.delegate == Some(authority.key()) @ ErrorCode::Unauthorized
+    )]
+    pub agent_registration: Account<'info, AgentRegistration>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VoteOnCoordination<'info> {
+    #[account(mut)]
+    pub coordination: Account<'info, Coordination>,
+    
+    #[account(mut, seeds = [b"swarm"], bump = swarm_registry.bump)]
+    pub swarm_registry: Account<'info, SwarmRegistry>,
+    
+    #[account(
+        constraint = agent_registration.agent_id == authority.key()
+            || agent_registration.delegate == Some(authority.key()) @ ErrorCode::Unauthorized
+    )]
+    pub agent_registration: Account<'info, AgentRegistration>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteCoordination<'info> {
+    #[account(mut)]
+    pub coordination: Account<'info, Coordination>,
+    
+    #[account(mut, seeds = [b"swarm"], bump = swarm_registry.bump)]
+    pub swarm_registry: Account<'info, SwarmRegistry>,
+    
+    /// Executing agent's registration
+    #[account(
+        mut,
+        constraint = agent_registration.agent_id == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub agent_registration: Account<'info, AgentRegistration>,
+    
+    #[account(seeds = [b"swarm_config"], bump = swarm_config.bump)]
+    pub swarm_config: Account<'info, SwarmConfig>,
+    
+    /// CHECK: Linked threat-intelligence Threat, validated by that program during the CPI
+    #[account(mut)]
+    pub threat: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: threat-intelligence ThreatCounter, validated by that program during the CPI
+    #[account(mut)]
+    pub threat_counter: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: threat-intelligence program, needed when `auto_neutralize_threats` is on
+    #[account(address = THREAT_INTELLIGENCE_PROGRAM_ID @ ErrorCode::InvalidThreatAccount)]
+    pub threat_program: Option<UncheckedAccount<'info>>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Heartbeat<'info> {
+    #[account(
+        mut,
+        constraint = agent_registration.agent_id == authority.key()
+            || agent_registration.delegate == Some(authority.key()) @ ErrorCode::Unauthorized
+    )]
+    pub agent_registration: Account<'info, AgentRegistration>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateReputation<'info> {
+    #[account(mut)]
+    pub agent_registration: Account<'info, AgentRegistration>,
+    
+    #[account(seeds = [b"swarm_config"], bump = swarm_config.bump)]
+    pub swarm_config: Account<'info, SwarmConfig>,
+    
+    #[account(seeds = [b"swarm"], bump = swarm_registry.bump, has_one = authority @ ErrorCode::Unauthorized)]
+    pub swarm_registry: Account<'info, SwarmRegistry>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddCoordinationComment<'info> {
+    #[account(mut)]
+    pub coordination: Account<'info, Coordination>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + CoordinationComment::INIT_SPACE,
+        seeds = [
+            b"coord_comment",
+            coordination.coordination_id.to_le_bytes().as_ref(),
+            coordination.comment_count.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub comment: Account<'info, CoordinationComment>,
+    
+    #[account(
+        constraint = agent_registration.agent_id == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub agent_registration: Account<'info, AgentRegistration>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApplyConfirmationReward<'info> {
+    #[account(mut)]
+    pub agent_registration: Account<'info, AgentRegistration>,
+    
+    #[account(
+        constraint = threat_counter.key()
+            == Pubkey::find_program_address(&[b"threat_counter"], &THREAT_INTELLIGENCE_PROGRAM_ID).0
+            @ ErrorCode::Unauthorized
+    )]
+    pub threat_counter: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DeactivateAgent<'info> {
+    #[account(mut)]
+    pub agent_registration: Account<'info, AgentRegistration>,
+    
+    #[account(mut, seeds = [b"swarm"], bump = swarm_registry.bump)]
+    pub swarm_registry: Account<'info, SwarmRegistry>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireCoordination<'info> {
+    #[account(mut)]
+    pub coordination: Account<'info, Coordination>,
+    
+    #[account(mut, seeds = [b"swarm"], bump = swarm_registry.bump)]
+    pub swarm_registry: Account<'info, SwarmRegistry>,
+}
+
+#[derive(Accounts)]
+pub struct LeaveCoordination<'info> {
+    #[account(mut)]
+    pub coordination: Account<'info, Coordination>,
+    
+    #[account(
+        mut,
+        constraint = agent_registration.agent_id == authority.key()
+            || agent_registration.delegate == Some(authority.key()) @ ErrorCode::Unauthorized
+    )]
+    pub agent_registration: Account<'info, AgentRegistration>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetCapabilityPrerequisites<'info> {
+    #[account(
+        mut,
+        seeds = [b"swarm"],
+        bump = swarm_registry.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub swarm_registry: Account<'info, SwarmRegistry>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateCapabilities<'info> {
+    #[account(
+        mut,
+        constraint = agent_registration.agent_id == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub agent_registration: Account<'info, AgentRegistration>,
+    
+    #[account(seeds = [b"swarm"], bump = swarm_registry.bump)]
+    pub swarm_registry: Account<'info, SwarmRegistry>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawStake<'info> {
+    #[account(
+        mut,
+        constraint = agent_registration.agent_id == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub agent_registration: Account<'info, AgentRegistration>,
+    
+    #[account(seeds = [b"swarm_config"], bump = swarm_config.bump)]
+    pub swarm_config: Account<'info, SwarmConfig>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SlashStake<'info> {
+    #[account(mut)]
+    pub agent_registration: Account<'info, AgentRegistration>,
+    
+    #[account(seeds = [b"swarm"], bump = swarm_registry.bump, has_one = authority @ ErrorCode::Unauthorized)]
+    pub swarm_registry: Account<'info, SwarmRegistry>,
+    
+    #[account(seeds = [b"swarm_config"], bump = swarm_config.bump)]
+    pub swarm_config: Account<'info, SwarmConfig>,
+    
+    /// CHECK: Burn address for slashed stake
+    #[account(mut, address = incinerator::ID)]
+    pub incinerator: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelCoordination<'info> {
+    #[account(
+        mut,
+        has_one = initiator @ ErrorCode::Unauthorized
+    )]
+    pub coordination: Account<'info, Coordination>,
+    
+    #[account(mut, seeds = [b"swarm"], bump = swarm_registry.bump)]
+    pub swarm_registry: Account<'info, SwarmRegistry>,
+    
+    pub initiator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MarkCoordinationFailed<'info> {
+    #[account(mut)]
+    pub coordination: Account<'info, Coordination>,
+    
+    #[account(seeds = [b"swarm"], bump = swarm_registry.bump)]
+    pub swarm_registry: Account<'info, SwarmRegistry>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(mut, seeds = [b"swarm_config"], bump = swarm_config.bump)]
+    pub swarm_config: Account<'info, SwarmConfig>,
+    
+    #[account(seeds = [b"swarm"], bump = swarm_registry.bump, has_one = authority @ ErrorCode::Unauthorized)]
+    pub swarm_registry: Account<'info, SwarmRegistry>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TransferSwarmAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"swarm"],
+        bump = swarm_registry.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub swarm_registry: Account<'info, SwarmRegistry>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptSwarmAuthority<'info> {
+    #[account(mut, seeds = [b"swarm"], bump = swarm_registry.bump)]
+    pub swarm_registry: Account<'info, SwarmRegistry>,
+    
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    #[account(
+        mut,
+        seeds = [b"swarm"],
+        bump = swarm_registry.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub swarm_registry: Account<'info, SwarmRegistry>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetCoordinationSummary<'info> {
+    pub coordination: Account<'info, Coordination>,
+}
+
+#[derive(Accounts)]
+pub struct CheckAgentEligibility<'info> {
+    pub coordination: Account<'info, Coordination>,
+    
+    #[account(seeds = [b"swarm"], bump = swarm_registry.bump)]
+    pub swarm_registry: Account<'info, SwarmRegistry>,
+    
+    pub agent_registration: Account<'info, AgentRegistration>,
+}
+
+#[derive(Accounts)]
+pub struct IsAgentLive<'info> {
+    pub agent_registration: Account<'info, AgentRegistration>,
+}
+
+#[derive(Accounts)]
+pub struct ComputeCrisisIndex<'info> {
+    #[account(seeds = [b"swarm"], bump = swarm_registry.bump)]
+    pub swarm_registry: Account<'info, SwarmRegistry>,
+    
+    #[account(seeds = [b"swarm_config"], bump = swarm_config.bump)]
+    pub swarm_config: Account<'info, SwarmConfig>,
+    
+    /// CHECK: threat-intelligence ThreatCounter, verified in `read_threat_aggregates`
+    pub threat_counter: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetUrgencyBreakdown<'info> {
+    #[account(seeds = [b"swarm"], bump = swarm_registry.bump)]
+    pub swarm_registry: Account<'info, SwarmRegistry>,
+}
+
+#[derive(Accounts)]
+pub struct SetDelegate<'info> {
+    #[account(
+        mut,
+        constraint = agent_registration.agent_id == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub agent_registration: Account<'info, AgentRegistration>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimBond<'info> {
+    #[account(
+        mut,
+        has_one = initiator @ ErrorCode::Unauthorized
+    )]
+    pub coordination: Account<'info, Coordination>,
+    
+    #[account(mut)]
+    pub initiator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SweepForfeitedBonds<'info> {
+    #[account(
+        mut,
+        seeds = [b"swarm"],
+        bump = swarm_registry.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub swarm_registry: Account<'info, SwarmRegistry>,
+    
+    #[account(mut)]
+    pub recipient: SystemAccount<'info>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AmendActionPlan<'info> {
+    #[account(
+        mut,
+        has_one = initiator @ ErrorCode::Unauthorized
+    )]
+    pub coordination: Account<'info, Coordination>,
+    
+    pub initiator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetAgentTier<'info> {
+    pub agent_registration: Account<'info, AgentRegistration>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyCoordinationResult<'info> {
+    pub coordination: Account<'info, Coordination>,
+    
+    pub verifier: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(agent: Pubkey)]
+pub struct BanAgent<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + BannedAgent::INIT_SPACE,
+        seeds = [b"banned", agent.as_ref()],
+        bump
+    )]
+    pub banned_agent: Account<'info, BannedAgent>,
+    
+    #[account(seeds = [b"swarm"], bump = swarm_registry.bump, has_one = authority @ ErrorCode::Unauthorized)]
+    pub swarm_registry: Account<'info, SwarmRegistry>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnbanAgent<'info> {
+    #[account(
+        mut,
+        seeds = [b"banned", banned_agent.agent.as_ref()],
+        bump = banned_agent.bump,
+        close = authority
+    )]
+    pub banned_agent: Account<'info, BannedAgent>,
+    
+    #[account(seeds = [b"swarm"], bump = swarm_registry.bump, has_one = authority @ ErrorCode::Unauthorized)]
+    pub swarm_registry: Account<'info, SwarmRegistry>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct BatchHeartbeat<'info> {
+    /// Owner or delegate of every agent in remaining_accounts
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimCoordinationReward<'info> {
+    #[account(mut)]
+    pub coordination: Account<'info, Coordination>,
+    
+    #[account(
+        mut,
+        constraint = agent_registration.agent_id == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub agent_registration: Account<'info, AgentRegistration>,
+    
+    #[account(seeds = [b"swarm_config"], bump = swarm_config.bump)]
+    pub swarm_config: Account<'info, SwarmConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetTypeDistribution<'info> {
+    #[account(seeds = [b"swarm"], bump = swarm_registry.bump)]
+    pub swarm_registry: Account<'info, SwarmRegistry>,
+}
+
+#[derive(Accounts)]
+pub struct AddRelatedThreat<'info> {
+    #[account(
+        mut,
+        has_one = initiator @ ErrorCode::Unauthorized
+    )]
+    pub coordination: Account<'info, Coordination>,
+    
+    pub initiator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RecomputePriority<'info> {
+    #[account(mut)]
+    pub coordination: Account<'info, Coordination>,
+    
+    /// CHECK: threat-intelligence Threat for `coordination.threat_id`, validated by `read_threat_severity`
+    pub threat: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterObserver<'info> {
+    #[account(mut)]
+    pub coordination: Account<'info, Coordination>,
+    
+    #[account(
+        constraint = agent_registration.agent_id == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub agent_registration: Account<'info, AgentRegistration>,
+    
+    pub authority: Signer<'info>,
+}
+
+// ============== HELPERS ==============
+
+/// Deserialize the canonical `[b"swarm"]` registry, failing with a clear
+/// error when the wrong account is passed or `initialize_swarm` hasn't run
+fn load_swarm_registry(info: &AccountInfo) -> Result<SwarmRegistry> {
+    let (expected, _) = Pubkey::find_program_address(&[b"swarm"], &crate::ID);
+    require_keys_eq!(info.key(), expected, ErrorCode::SwarmNotInitialized);
+    require!(
+        info.owner == &crate::ID && !info.data_is_empty(),
+        ErrorCode::SwarmNotInitialized
+    );
+
+    SwarmRegistry::try_deserialize(&mut &info.try_borrow_data()?[..])
+}
+
+/// CPI the registered callback program's `on_coordination_resolved` instruction
+/// with the coordination outcome. The coordination is passed read-only
+fn invoke_outcome_callback<'info>(
+    coordination: &mut Account<'info, Coordination>,
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    let callback = coordination.callback.ok_or(ErrorCode::CallbackProgramMissing)?;
+
+    // Reentrancy guard: the outcome is delivered at most once
+    require!(!coordination.callback_invoked, ErrorCode::CallbackAlreadyInvoked);
+    coordination.callback_invoked = true;
+
+    let target = remaining_accounts
+        .first()
+        .filter(|info| info.key() == callback.target_program && info.executable)
+        .ok_or(ErrorCode::CallbackProgramMissing)?;
+
+    let outcome = CoordinationOutcome {
+        coordination_id: callback.coordination_id,
+        threat_id: coordination.threat_id,
+        status: coordination.status,
+    };
+    let mut data = hash(b"global:on_coordination_resolved").to_bytes()[..8].to_vec();
+    outcome.serialize(&mut data)?;
+
+    // Persist the resolved state so the callee reads the final outcome
+    coordination.exit(&crate::ID)?;
+
+    let ix = Instruction {
+        program_id: callback.target_program,
+        accounts: vec![AccountMeta::new_readonly(coordination.key(), false)],
+        data,
+    };
+
+    let budget_before = sol_remaining_compute_units();
+    require!(
+        budget_before >= CALLBACK_COMPUTE_LIMIT,
+        ErrorCode::CallbackBudgetExceeded
+    );
+    invoke(&ix, &[coordination.to_account_info(), target.clone()])?;
+    let compute_units = budget_before.saturating_sub(sol_remaining_compute_units());
+    require!(
+        compute_units <= CALLBACK_COMPUTE_LIMIT,
+        ErrorCode::CallbackBudgetExceeded
+    );
+
+    emit!(CallbackInvoked {
+        coordination_id: callback.coordination_id,
+        target_program: callback.target_program,
+        status: coordination.status,
+        compute_units,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// CPI threat-intelligence's `neutralize_from_coordination` for a fully successful
+/// coordination, signed by the swarm registry PDA. Built by hand since that crate
+/// depends on this one
+fn neutralize_linked_threat<'info>(
+    coordination: &mut Account<'info, Coordination>,
+    swarm: &Account<'info, SwarmRegistry>,
+    threat: &AccountInfo<'info>,
+    threat_counter: &AccountInfo<'info>,
+    threat_program: &AccountInfo<'info>,
+) -> Result<()> {
+    // Persist the executed state so the callee sees the final outcome
+    coordination.exit(&crate::ID)?;
+
+    let ix = Instruction {
+        program_id: THREAT_INTELLIGENCE_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(threat.key(), false),
+            AccountMeta::new_readonly(coordination.key(), false),
+            AccountMeta::new_readonly(swarm.key(), true),
+            AccountMeta::new(threat_counter.key(), false),
+        ],
+        data: hash(b"global:neutralize_from_coordination").to_bytes()[..8].to_vec(),
+    };
+    invoke_signed(
+        &ix,
+        &[
+            threat.clone(),
+            coordination.to_account_info(),
+            swarm.to_account_info(),
+            threat_counter.clone(),
+            threat_program.clone(),
+        ],
+        &[&[b"swarm", &[swarm.bump]]],
+    )?;
+
+    emit!(LinkedThreatNeutralized {
+        coordination_id: coordination.coordination_id,
+        threat_id: coordination.threat_id,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Deserialize the canonical `[b"swarm_config"]` account, with the same
+/// first-run error as `load_swarm_registry`
+fn load_swarm_config(info: &AccountInfo) -> Result<SwarmConfig> {
+    let (expected, _) = Pubkey::find_program_address(&[b"swarm_config"], &crate::ID);
+    require_keys_eq!(info.key(), expected, ErrorCode::SwarmNotInitialized);
+    require!(
+        info.owner == &crate::ID && !info.data_is_empty(),
+        ErrorCode::SwarmNotInitialized
+    );
+
+    SwarmConfig::try_deserialize(&mut &info.try_borrow_data()?[..])
+}
+
+/// Capabilities an agent of the given type is allowed to declare
+fn allowed_capabilities(agent_type: AgentType) -> &'static [Capability] {
+    use Capability::*;
+    match agent_type {
+        AgentType::Sentinel => &[TransactionMonitoring, ThreatDetection],
+        AgentType::Scanner => &[ContractAnalysis, ThreatDetection],
+        AgentType::Guardian => &[ThreatDetection, TransactionMonitoring],
+        AgentType::Oracle => &[RiskPrediction, ContractAnalysis],
+        AgentType::Intel => &[KnowledgeManagement, ActorTracking],
+        AgentType::Reporter => &[CommunityAlerts],
+        AgentType::Auditor => &[ReasoningVerification, ContractAnalysis],
+        AgentType::Hunter => &[ActorTracking, TransactionMonitoring],
+        AgentType::Healer => &[FundRecovery, ThreatDetection],
+        AgentType::Coordinator => &[SwarmCoordination, KnowledgeManagement],
+    }
+}
+
+/// Bonus for an agent covering its type's primary capability (e.g. a Healer on FundRecovery)
+fn specialization_bonus(agent_type: AgentType, capability: Capability) -> u8 {
+    if allowed_capabilities(agent_type).first() == Some(&capability) {
+        SPECIALIZATION_BONUS
+    } else {
+        0
+    }
+}
+
+/// Reject capabilities outside the declared agent type's role
+fn capabilities_valid_for_type(agent_type: AgentType, capabilities: &[Capability]) -> Result<()> {
+    let allowed = allowed_capabilities(agent_type);
+    require!(
+        capabilities.iter().all(|capability| allowed.contains(capability)),
+        ErrorCode::CapabilityTypeMismatch
+    );
+    Ok(())
+}
+
+/// Urgency implied by a threat's 0-100 severity
+pub fn urgency_from_severity(severity: u8) -> Urgency {
+    match severity {
+        0..=25 => Urgency::Low,
+        26..=50 => Urgency::Medium,
+        51..=80 => Urgency::High,
+        _ => Urgency::Critical,
+    }
+}
+
+/// Read a threat-intelligence Threat's severity without depending on that crate.
+/// Layout: 8-byte discriminator, threat_id: u64, threat_type: u8 enum tag, severity: u8
+fn read_threat_severity(info: &AccountInfo, threat_id: u64) -> Result<u8> {
+    let (expected, _) = Pubkey::find_program_address(
+        &[b"threat", threat_id.to_le_bytes().as_ref()],
+        &THREAT_INTELLIGENCE_PROGRAM_ID,
+    );
+    require_keys_eq!(info.key(), expected, ErrorCode::InvalidThreatAccount);
+    require!(
+        info.owner == &THREAT_INTELLIGENCE_PROGRAM_ID,
+        ErrorCode::InvalidThreatAccount
+    );
+
+    let data = info.try_borrow_data()?;
+    require!(data.len() > 17, ErrorCode::InvalidThreatAccount);
+    Ok(data[17])
+}
+
+/// Read the ThreatCounter's (active_threats, active_severity_sum) without depending
+/// on the threat-intelligence crate; offsets are pinned by a test in that crate
+fn read_threat_aggregates(info: &AccountInfo) -> Result<(u64, u64)> {
+    let (expected, _) =
+        Pubkey::find_program_address(&[b"threat_counter"], &THREAT_INTELLIGENCE_PROGRAM_ID);
+    require_keys_eq!(info.key(), expected, ErrorCode::InvalidThreatAccount);
+    require!(
+        info.owner == &THREAT_INTELLIGENCE_PROGRAM_ID,
+        ErrorCode::InvalidThreatAccount
+    );
+
+    let data = info.try_borrow_data()?;
+    require!(data.len() >= THREAT_COUNTER_MIN_LEN, ErrorCode::InvalidThreatAccount);
+    let read_u64 = |offset: usize| {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&data[offset..offset + 8]);
+        u64::from_le_bytes(bytes)
+    };
+    Ok((
+        read_u64(THREAT_COUNTER_ACTIVE_THREATS_OFFSET),
+        read_u64(THREAT_COUNTER_ACTIVE_SEVERITY_OFFSET),
+    ))
+}
+
+/// Live threats' mean severity, scaled by how close their number is to
+/// CRISIS_SATURATION_THREATS, 0-100
+fn threat_pressure(active_threats: u64, active_severity_sum: u64) -> u8 {
+    if active_threats == 0 {
+        return 0;
+    }
+    let mean = active_severity_sum as u128 / active_threats as u128;
+    let volume = active_threats.min(CRISIS_SATURATION_THREATS) as u128;
+    (mean * volume / CRISIS_SATURATION_THREATS as u128).min(100) as u8
+}
+
+/// Mean urgency of active coordinations, 0-100, from [low, medium, high, critical] counts
+fn urgency_pressure(active: [u64; 4]) -> u8 {
+    const LEVEL_SCORES: [u128; 4] = [25, 50, 75, 100];
+    let total: u128 = active.iter().map(|count| *count as u128).sum();
+    if total == 0 {
+        return 0;
+    }
+    let weighted: u128 = active
+        .iter()
+        .zip(LEVEL_SCORES)
+        .map(|(count, score)| *count as u128 * score)
+        .sum();
+    (weighted / total) as u8
+}
+
+/// Blend the two pressures by percentage weights that sum to 100
+fn crisis_index(
+    threat_pressure: u8,
+    urgency_pressure: u8,
+    threat_weight: u8,
+    urgency_weight: u8,
+) -> u8 {
+    let blended = threat_pressure as u16 * threat_weight as u16
+        + urgency_pressure as u16 * urgency_weight as u16;
+    (blended / 100).min(100) as u8
+}
+
+/// Check an approved coordination is still quorate and staffed, and that
+/// `executor` is one of its participants
+fn ensure_executable(coordination: &Coordination, executor: &Pubkey) -> Result<()> {
+    require!(
+        coordination.status == CoordinationStatus::Approved,
+        ErrorCode::NotApproved
+    );
+    require!(
+        coordination.participating_agents.contains(executor),
+        ErrorCode::NotParticipant
+    );
+    require!(
+        coordination.participating_agents.len() >= coordination.min_participants as usize,
+        ErrorCode::QuorumNotMet
+    );
+    // Participants may have left since approval; make sure every step is still staffed
+    if coordination.require_full_capability_coverage {
+        require!(
+            coordination
+                .required_capabilities
+                .iter()
+                .all(|req| coordination.contributed_capabilities.contains(req)),
+            ErrorCode::IncompleteCoverage
+        );
+    }
+    Ok(())
+}
+
+/// Record the vote of the participant at `position`. The vote carries the
+/// reputation snapshotted when the agent joined, the same value counted into
+/// `total_reputation_weight`, so later reputation changes can't shift the outcome
+fn tally_vote(coordination: &mut Coordination, position: usize, approve: bool) -> Result<()> {
+    let voter = coordination.participating_agents[position];
+    let snapshot = VoteWeight {
+        voter,
+        weight: coordination.join_weights[position],
+    };
+    coordination.vote_weights.push(snapshot);
+    let weight = snapshot.weight as u64;
+
+    if approve {
+        coordination.votes_for = coordination
+            .votes_for
+            .checked_add(1)
+            .ok_or(ErrorCode::VoteOverflow)?;
+        coordination.weighted_for += weight;
+    } else {
+        coordination.votes_against = coordination
+            .votes_against
+            .checked_add(1)
+            .ok_or(ErrorCode::VoteOverflow)?;
+        coordination.weighted_against += weight;
+    }
+    coordination.voters.push(voter);
+    coordination.vote_record.push(VoteRecord { voter, approve });
+    Ok(())
+}
+
+/// Approval needs a strict majority of the participants' combined weight
+fn weighted_majority(weighted_for: u64, total_weight: u64) -> bool {
+    weighted_for > total_weight / 2
+}
+
+/// Share of `staked` burned at `percent`, widened to u128 so large stakes can't overflow
+fn slash_amount(staked: u64, percent: u8) -> u64 {
+    (staked as u128 * percent as u128 / 100) as u64
+}
+
+/// Failure penalty scaled by how many capabilities the agent declares:
+/// `base * clamp(capability_count, 1, MAX_PENALTY_CAPABILITY_MULTIPLIER)`, saturating at u8::MAX.
+/// A single-capability specialist pays the base penalty; an agent with three or more pays triple
+fn capability_scaled_penalty(base: u8, capability_count: usize) -> u8 {
+    let multiplier = capability_count.clamp(1, MAX_PENALTY_CAPABILITY_MULTIPLIER as usize) as u8;
+    base.saturating_mul(multiplier)
+}
+
+/// Triage ordering for pending coordinations: urgency dominates, severity breaks ties
+fn priority_score(urgency: Urgency, severity: u8) -> u64 {
+    (urgency as u64 + 1) * 1_000 + severity as u64
+}
+
+/// Classify a raw reputation score into its policy tier
+pub fn reputation_tier(score: u8) -> ReputationTier {
+    match score {
+        0..=24 => ReputationTier::Untrusted,
+        25..=49 => ReputationTier::Probationary,
+        50..=79 => ReputationTier::Trusted,
+        _ => ReputationTier::Elite,
+    }
+}
+
+/// Minimum tier needed to join a coordination of the given urgency
+fn min_tier_for(urgency: Urgency) -> ReputationTier {
+    match urgency {
+        Urgency::Low | Urgency::Medium => ReputationTier::Untrusted,
+        Urgency::High => ReputationTier::Trusted,
+        Urgency::Critical => ReputationTier::Elite,
+    }
+}
+
+/// The registry's active-coordination counter for an urgency level
+fn active_for_urgency(swarm: &mut SwarmRegistry, urgency: Urgency) -> &mut u64 {
+    match urgency {
+        Urgency::Low => &mut swarm.active_low,
+        Urgency::Medium => &mut swarm.active_medium,
+        Urgency::High => &mut swarm.active_high,
+        Urgency::Critical => &mut swarm.active_critical,
+    }
+}
+
+/// Who may bring `agent` back: the swarm authority always, the agent itself
+/// only when it wasn't the swarm authority that retired it
+fn can_reactivate(agent: &AgentRegistration, signer: Pubkey, swarm_authority: Pubkey) -> bool {
+    signer == swarm_authority
+        || (signer == agent.agent_id && agent.deactivated_by != Some(swarm_authority))
+}
+
+/// Reputation after adding (or removing) `amount`, kept within 0-100, and the change applied
+fn reputation_delta(score: u8, amount: u8, clawback: bool) -> (u8, u8) {
+    let new_score = if clawback {
+        score.saturating_sub(amount)
+    } else {
+        std::cmp::min(100, score.saturating_add(amount))
+    };
+    (new_score, score.abs_diff(new_score))
+}
+
+/// Drop a resolved coordination from its urgency counter
+fn release_active_urgency(swarm: &mut SwarmRegistry, urgency: Urgency) {
+    let counter = active_for_urgency(swarm, urgency);
+    *counter = counter.saturating_sub(1);
+}
+
+/// Bitmask of capabilities, indexed by `Capability as u16`
+fn capability_mask(capabilities: &[Capability]) -> u16 {
+    capabilities
+        .iter()
+        .fold(0, |mask, capability| mask | 1 << (*capability as u16))
+}
+
+/// Ensure every declared capability comes with its configured prerequisites
+fn check_capability_prerequisites(
+    prerequisites: &[u16; 10],
+    capabilities: &[Capability],
+) -> Result<()> {
+    let declared = capability_mask(capabilities);
+    for capability in capabilities {
+        let required = prerequisites[*capability as usize];
+        require!(
+            declared & required == required,
+            ErrorCode::MissingPrerequisiteCapability
+        );
+    }
+    Ok(())
+}
+
+/// Shared body of `register_agent` and `register_agent_with_reputation`
+fn process_register_agent(
+    ctx: Context<RegisterAgent>,
+    agent_type: AgentType,
+    capabilities: Vec<Capability>,
+    initial_reputation: u8,
+    stake_amount: u64,
+) -> Result<()> {
+    require!(capabilities.len() <= 10, ErrorCode::TooManyCapabilities);
+    require!(ctx.accounts.banned_agent.data_is_empty(), ErrorCode::AgentBanned);
+
+    // Stake is held in the registration PDA on top of its rent
+    if stake_amount > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ctx.accounts.agent_registration.to_account_info(),
+                },
+            ),
+            stake_amount,
+        )?;
+    }
+
+    let swarm_info = ctx.accounts.swarm_registry.to_account_info();
+    let mut swarm = load_swarm_registry(&swarm_info)?;
+    capabilities_valid_for_type(agent_type, &capabilities)?;
+    check_capability_prerequisites(&swarm.capability_prerequisites, &capabilities)?;
+
+    let agent = &mut ctx.accounts.agent_registration;
+    let clock = Clock::get()?;
+
+    agent.agent_id = ctx.accounts.agent_authority.key();
+    agent.agent_type = agent_type;
+    agent.capabilities = capabilities.clone();
+    agent.registered_at = clock.unix_timestamp;
+    agent.last_active = clock.unix_timestamp;
+    agent.active = true;
+    agent.total_actions = 0;
+    agent.successful_actions = 0;
+    agent.coordinations_joined = 0;
+    agent.coordinations_executed = 0;
+    agent.reputation_score = initial_reputation;
+    agent.staked = stake_amount;
+    agent.delegate = None;
+    agent.deactivated_by = None;
+    agent.bump = ctx.bumps.agent_registration;
+
+    swarm.total_agents += 1;
+    swarm.type_counts[agent_type as usize] += 1;
+    swarm.try_serialize(&mut &mut swarm_info.try_borrow_mut_data()?[..])?;
+
+    emit!(AgentRegistered {
+        agent_id: agent.agent_id,
+        agent_type,
+        capabilities,
+        reputation_score: initial_reputation,
+        timestamp: clock.unix_timestamp,
+    });
+
+    if stake_amount > 0 {
+        emit!(StakeDeposited {
+            agent_id: agent.agent_id,
+            amount: stake_amount,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    msg!("Registered {:?} agent: {}", agent_type, agent.agent_id);
+    Ok(())
+}
+
+/// Move an abandoned coordination's bond into the swarm registry, where it
+/// waits for `sweep_forfeited_bonds`
+fn forfeit_bond(
+    coordination: &mut Coordination,
+    coordination_info: &AccountInfo,
+    swarm: &mut SwarmRegistry,
+    swarm_info: &AccountInfo,
+) -> Result<u64> {
+    let amount = coordination.bond;
+    if amount > 0 {
+        coordination.bond = 0;
+        coordination_info.sub_lamports(amount)?;
+        swarm_info.add_lamports(amount)?;
+        swarm.forfeited_bonds += amount;
+    }
+    Ok(amount)
+}
+
+/// Return a decided coordination's bond to its initiator
+fn refund_bond(
+    coordination: &mut Coordination,
+    coordination_info: &AccountInfo,
+    initiator_info: &AccountInfo,
+) -> Result<u64> {
+    let amount = coordination.bond;
+    // Failed coordinations were executed first, so they still count as completed
+    require!(
+        matches!(
+            coordination.status,
+            CoordinationStatus::Executed | CoordinationStatus::Failed | CoordinationStatus::Rejected
+        ),
+        ErrorCode::BondNotRefundable
+    );
+    require!(amount > 0, ErrorCode::NoBond);
+
+    coordination.bond = 0;
+    coordination_info.sub_lamports(amount)?;
+    initiator_info.add_lamports(amount)?;
+    Ok(amount)
+}
+
+/// Pay out every forfeited bond held by the swarm registry
+fn sweep_bonds(
+    swarm: &mut SwarmRegistry,
+    swarm_info: &AccountInfo,
+    recipient_info: &AccountInfo,
+) -> Result<u64> {
+    let amount = swarm.forfeited_bonds;
+    require!(amount > 0, ErrorCode::NoForfeitedBonds);
+
+    swarm.forfeited_bonds = 0;
+    swarm_info.sub_lamports(amount)?;
+    recipient_info.add_lamports(amount)?;
+    Ok(amount)
+}
+
+// ============== STATE ==============
+
+#[account]
+#[derive(InitSpace)]
+pub struct SwarmRegistry {
+    pub authority: Pubkey,
+    pub total_agents: u64,
+    pub type_counts: [u64; 10], // active agents per AgentType
+    pub active_coordinations: u64,
+    pub active_low: u64, // active_coordinations split by urgency
+    pub active_medium: u64,
+    pub active_high: u64,
+    pub active_critical: u64,
+    pub total_response_latency: u64, // seconds, summed over executed coordinations
+    pub executed_count: u64,
+    pub total_coordinations: u64,
+    pub capability_prerequisites: [u16; 10], // per-capability bitmask of required capabilities
+    pub pending_authority: Option<Pubkey>, // proposed successor awaiting acceptance
+    pub paused: bool, // emergency halt on new coordination activity
+    pub forfeited_bonds: u64, // forfeited bond lamports held here until swept
+    pub bump: u8,
+}
+
+/// All coordinator-side tunables, managed through `update_config`
+#[account]
+#[derive(InitSpace)]
+pub struct SwarmConfig {
+    pub params: SwarmParams,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct AgentRegistration {
+    pub agent_id: Pubkey,
+    pub agent_type: AgentType,
+    #[max_len(10)]
+    pub capabilities: Vec<Capability>,
+    pub registered_at: i64,
+    pub last_active: i64,
+    pub active: bool,
+    pub total_actions: u64,
+    pub successful_actions: u64,
+    pub coordinations_joined: u64,
+    pub coordinations_executed: u64,
+    pub reputation_score: u8, // 0-100
+    pub staked: u64, // lamports held in this PDA beyond rent
+    pub delegate: Option<Pubkey>, // hot key allowed to heartbeat, join and vote
+    pub deactivated_by: Option<Pubkey>, // signer of the last deactivation, while inactive
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Coordination {
+    pub coordination_id: u64,
+    pub threat_id: u64,
+    #[max_len(5)]
+    pub related_threat_ids: Vec<u64>, // other threats from the same incident
+    pub initiator: Pubkey,
+    #[max_len(5)]
+    pub required_capabilities: Vec<Capability>,
+    #[max_len(1000)]
+    pub action_plan: String,
+    pub amendment_count: u8,
+    pub urgency: Urgency,
+    pub priority_score: u64, // higher is staffed first; see `priority_score`
+    pub status: CoordinationStatus,
+    #[max_len(10)]
+    pub participating_agents: Vec<Pubkey>,
+    #[max_len(10)]
+    pub contributed_capabilities: Vec<Capability>, // parallel to participating_agents
+    #[max_len(10)]
+    pub join_weights: Vec<u8>, // reputation snapshotted at join, parallel to participating_agents
+    pub require_full_capability_coverage: bool, // execution needs every required capability
+    pub coordination_coverage_score: u64, // summed specialization bonuses of participants
+    pub response_latency_seconds: i64, // initiation to execution
+    pub min_participants: u8,
+    pub max_participants: u8, // initiator's team-size cap, at most 10
+    #[max_len(10)]
+    pub voters: Vec<Pubkey>,
+    #[max_len(10)]
+    pub vote_weights: Vec<VoteWeight>, // reputation snapshot per voter
+    #[max_len(10)]
+    pub vote_record: Vec<VoteRecord>, // direction of each cast vote
+    pub votes_for: u8,
+    pub votes_against: u8,
+    pub weighted_for: u64,
+    pub weighted_against: u64,
+    pub total_reputation_weight: u64, // combined reputation of participants
+    pub initiated_at: i64,
+    pub deadline: i64,
+    pub executed_at: Option<i64>,
+    pub result_hash: Option<[u8; 32]>,
+    pub comment_count: u32,
+    #[max_len(200)]
+    pub cancel_reason: String,
+    #[max_len(300)]
+    pub failure_reason: String,
+    pub callback: Option<CoordinationCallback>,
+    pub callback_invoked: bool,
+    pub executed_by: Pubkey,
+    pub outcome: Option<ExecutionOutcome>,
+    #[max_len(10)]
+    pub rewarded_agents: Vec<Pubkey>, // participants that claimed their success reward
+    #[max_len(5)]
+    pub observers: Vec<Pubkey>, // agents that reviewed without joining; no vote
+    pub bond: u64, // lamports held in this PDA beyond rent until reclaimed or forfeited
+    pub bump: u8,
+}
+
+/// Marks a key the swarm authority has barred from registering
+#[account]
+#[derive(InitSpace)]
+pub struct BannedAgent {
+    pub agent: Pubkey,
+    pub banned_by: Pubkey,
+    pub banned_at: i64,
+    pub bump: u8,
+}
+
+/// Records which coordination an initiator's idempotency key produced
+#[account]
+#[derive(InitSpace)]
+pub struct InitiationReceipt {
+    pub initiator: Pubkey,
+    pub idempotency_key: [u8; 16],
+    pub coordination_id: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct CoordinationComment {
+    pub coordination_id: u64,
+    pub index: u32,
+    pub author: Pubkey,
+    #[max_len(500)]
+    pub text: String,
+    pub created_at: i64,
+    pub bump: u8,
+}
 
 // ============== TYPES ==============
 
@@ -448,6 +2738,17 @@ pub enum Urgency {
     Critical,
 }
 
+/// Reputation bands, ordered from least to most trusted
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, InitSpace, Debug,
+)]
+pub enum ReputationTier {
+    Untrusted,
+    Probationary,
+    Trusted,
+    Elite,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
 pub enum CoordinationStatus {
     Pending,
@@ -458,6 +2759,90 @@ pub enum CoordinationStatus {
     Cancelled,
 }
 
+/// A voter's reputation captured when their vote was cast
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct VoteWeight {
+    pub voter: Pubkey,
+    pub weight: u8,
+}
+
+/// Which way a participant voted, kept for auditing the decision
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct VoteRecord {
+    pub voter: Pubkey,
+    pub approve: bool,
+}
+
+/// Result reported when a coordination is executed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum ExecutionOutcome {
+    FullSuccess,
+    PartialSuccess,
+    Failed,
+}
+
+/// Program notified via CPI when a coordination reaches consensus
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct CoordinationCallback {
+    pub target_program: Pubkey,
+    pub coordination_id: u64,
+}
+
+/// Payload delivered to `on_coordination_resolved` in the callback program
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct CoordinationOutcome {
+    pub coordination_id: u64,
+    pub threat_id: u64,
+    pub status: CoordinationStatus,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct SwarmParams {
+    pub min_quorum: u8,              // floor for a coordination's min_participants
+    pub probation_reputation: u8,    // starting reputation for register_agent; trust is earned
+    pub reputation_reward: u8,       // update_reputation gain on success
+    pub reputation_penalty: u8,      // update_reputation loss on failure
+    pub min_withdraw_reputation: u8, // needed to withdraw stake
+    pub stake_slash_percent: u8,     // share of stake burned by slash_stake
+    pub min_expiry_seconds: i64,     // bounds on a coordination's deadline
+    pub max_expiry_seconds: i64,
+    pub coordination_bond: u64,      // lamports locked by initiate_coordination
+    pub auto_neutralize_threats: bool, // FullSuccess execution neutralizes the linked threat
+    pub scale_penalty_by_capabilities: bool, // see `capability_scaled_penalty`
+    pub crisis_threat_weight: u8,    // compute_crisis_index weight on threat pressure, percent
+    pub crisis_urgency_weight: u8,   // weight on coordination urgency; the two sum to 100
+}
+
+impl Default for SwarmParams {
+    fn default() -> Self {
+        Self {
+            min_quorum: 1,
+            probation_reputation: 25,
+            reputation_reward: 1,
+            reputation_penalty: 5,
+            min_withdraw_reputation: 50,
+            stake_slash_percent: 50,
+            min_expiry_seconds: 60,
+            max_expiry_seconds: 7 * 24 * 60 * 60,
+            coordination_bond: 10_000_000,
+            auto_neutralize_threats: false,
+            scale_penalty_by_capabilities: false,
+            crisis_threat_weight: 60,
+            crisis_urgency_weight: 40,
+        }
+    }
+}
+
+/// Computed voting progress returned by get_coordination_summary
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CoordinationSummary {
+    pub participant_count: u8,
+    pub votes_cast: u8,
+    pub votes_remaining: u8,
+    pub approval_ratio: u16, // weighted approval in basis points
+    pub is_quorum_met: bool,
+}
+
 // ============== EVENTS ==============
 
 #[event]
@@ -465,6 +2850,7 @@ pub struct AgentRegistered {
     pub agent_id: Pubkey,
     pub agent_type: AgentType,
     pub capabilities: Vec<Capability>,
+    pub reputation_score: u8,
     pub timestamp: i64,
 }
 
@@ -474,6 +2860,8 @@ pub struct CoordinationInitiated {
     pub threat_id: u64,
     pub initiator: Pubkey,
     pub urgency: Urgency,
+    pub urgency_derived: bool, // derived from the threat's severity
+    pub priority_score: u64,
     pub timestamp: i64,
 }
 
@@ -481,6 +2869,16 @@ pub struct CoordinationInitiated {
 pub struct AgentJoinedCoordination {
     pub coordination_id: u64,
     pub agent_id: Pubkey,
+    pub contributed_capability: Capability,
+    pub coordination_coverage_score: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VoteCast {
+    pub coordination_id: u64,
+    pub agent: Pubkey,
+    pub approve: bool,
     pub timestamp: i64,
 }
 
@@ -489,6 +2887,8 @@ pub struct CoordinationApproved {
     pub coordination_id: u64,
     pub votes_for: u8,
     pub votes_against: u8,
+    pub weighted_for: u64,
+    pub weighted_against: u64,
     pub timestamp: i64,
 }
 
@@ -497,6 +2897,8 @@ pub struct CoordinationRejected {
     pub coordination_id: u64,
     pub votes_for: u8,
     pub votes_against: u8,
+    pub weighted_for: u64,
+    pub weighted_against: u64,
     pub timestamp: i64,
 }
 
@@ -505,6 +2907,9 @@ pub struct CoordinationExecuted {
     pub coordination_id: u64,
     pub threat_id: u64,
     pub result_hash: [u8; 32],
+    pub executed_by: Pubkey,
+    pub outcome: ExecutionOutcome,
+    pub response_latency_seconds: i64,
     pub timestamp: i64,
 }
 
@@ -516,6 +2921,233 @@ pub struct ReputationUpdated {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct CoordinationCommented {
+    pub coordination_id: u64,
+    pub index: u32,
+    pub author: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentDeactivated {
+    pub agent_id: Pubkey,
+    pub deactivated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentReactivated {
+    pub agent_id: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CoordinationExpired {
+    pub coordination_id: u64,
+    pub deadline: i64,
+    pub bond_forfeited: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentLeftCoordination {
+    pub coordination_id: u64,
+    pub agent_id: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CallbackInvoked {
+    pub coordination_id: u64,
+    pub target_program: Pubkey,
+    pub status: CoordinationStatus,
+    pub compute_units: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CapabilityPrerequisitesUpdated {
+    pub capability: Capability,
+    pub prerequisites: Vec<Capability>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CapabilitiesUpdated {
+    pub agent_id: Pubkey,
+    pub old_capabilities: Vec<Capability>,
+    pub new_capabilities: Vec<Capability>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakeDeposited {
+    pub agent_id: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakeWithdrawn {
+    pub agent_id: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakeSlashed {
+    pub agent_id: Pubkey,
+    pub amount: u64,
+    pub remaining: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CoordinationCancelled {
+    pub coordination_id: u64,
+    pub reason: String,
+    pub bond_forfeited: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CoordinationFailed {
+    pub coordination_id: u64,
+    pub threat_id: u64,
+    pub failure_reason: String,
+    pub participating_agents: Vec<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub params: SwarmParams,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityTransferProposed {
+    pub current_authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PauseStateChanged {
+    pub paused: bool,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DelegateUpdated {
+    pub agent_id: Pubkey,
+    pub delegate: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BondReclaimed {
+    pub coordination_id: u64,
+    pub initiator: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ForfeitedBondsSwept {
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ActionPlanAmended {
+    pub coordination_id: u64,
+    pub revision: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CoordinationResultVerified {
+    pub coordination_id: u64,
+    pub verifier: Pubkey,
+    pub verified: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentBanned {
+    pub agent: Pubkey,
+    pub banned_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentUnbanned {
+    pub agent: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReputationParamsUpdated {
+    pub reward_delta: u8,
+    pub penalty_delta: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BatchHeartbeatRecorded {
+    pub authority: Pubkey,
+    pub agent_ids: Vec<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CoordinationRewardClaimed {
+    pub coordination_id: u64,
+    pub agent_id: Pubkey,
+    pub new_score: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RelatedThreatAdded {
+    pub coordination_id: u64,
+    pub threat_id: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PriorityRecomputed {
+    pub coordination_id: u64,
+    pub old_score: u64,
+    pub new_score: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ObserverRegistered {
+    pub coordination_id: u64,
+    pub agent_id: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LinkedThreatNeutralized {
+    pub coordination_id: u64,
+    pub threat_id: u64,
+    pub timestamp: i64,
+}
+
 // ============== ERRORS ==============
 
 #[error_code]
@@ -532,4 +3164,376 @@ pub enum ErrorCode {
     NotApproved,
     #[msg("Unauthorized for this operation")]
     Unauthorized,
+    #[msg("Swarm registry is not initialized; call initialize_swarm first")]
+    SwarmNotInitialized,
+    #[msg("Comment exceeds maximum length")]
+    CommentTooLong,
+    #[msg("Coordination has already been resolved")]
+    CoordinationResolved,
+    #[msg("Agent is not active")]
+    AgentInactive,
+    #[msg("Agent has not been deactivated")]
+    AgentNotDeactivated,
+    #[msg("Vote tally overflowed")]
+    VoteOverflow,
+    #[msg("Reputation must be between 0 and 100")]
+    InvalidReputation,
+    #[msg("Expiry is outside the configured bounds")]
+    InvalidExpiry,
+    #[msg("Coordination deadline has not passed yet")]
+    NotExpired,
+    #[msg("Coordination is no longer pending")]
+    CoordinationLocked,
+    #[msg("Voting has already started on this coordination")]
+    VotingStarted,
+    #[msg("Registered callback program was not supplied")]
+    CallbackProgramMissing,
+    #[msg("Coordination callback has already been invoked")]
+    CallbackAlreadyInvoked,
+    #[msg("Coordination callback exceeded its compute budget")]
+    CallbackBudgetExceeded,
+    #[msg("Agent has already voted on this coordination")]
+    AlreadyVoted,
+    #[msg("Minimum participants must be between the configured quorum floor and 10")]
+    InvalidQuorum,
+    #[msg("Coordination has not reached its participant quorum")]
+    QuorumNotMet,
+    #[msg("Declared capability is missing a prerequisite capability")]
+    MissingPrerequisiteCapability,
+    #[msg("A coordination was already initiated with this idempotency key")]
+    DuplicateInitiation,
+    #[msg("Agent must be deactivated first")]
+    AgentStillActive,
+    #[msg("Agent has no stake")]
+    NoStake,
+    #[msg("Agent reputation is too low for this operation")]
+    ReputationTooLow,
+    #[msg("Stake can only be slashed at zero reputation")]
+    ReputationNotZero,
+    #[msg("Reason exceeds maximum length")]
+    ReasonTooLong,
+    #[msg("Only pending coordinations can be cancelled")]
+    CannotCancel,
+    #[msg("Coordination has not been executed")]
+    NotExecuted,
+    #[msg("Swarm config value out of range")]
+    InvalidConfig,
+    #[msg("Capability is not allowed for this agent type")]
+    CapabilityTypeMismatch,
+    #[msg("Signer is not the pending swarm authority")]
+    NotPendingAuthority,
+    #[msg("Swarm is paused")]
+    Paused,
+    #[msg("Participants do not cover every required capability")]
+    IncompleteCoverage,
+    #[msg("Threat account required to derive urgency")]
+    ThreatAccountMissing,
+    #[msg("Account is not the threat-intelligence Threat for this threat id")]
+    InvalidThreatAccount,
+    #[msg("Bond is only refunded once the coordination is executed or rejected")]
+    BondNotRefundable,
+    #[msg("Coordination has no bond to reclaim")]
+    NoBond,
+    #[msg("Action plan exceeds 1000 characters")]
+    ActionPlanTooLong,
+    #[msg("Action plan can't be amended once voting has started")]
+    PlanLocked,
+    #[msg("Action plan amendment limit reached")]
+    TooManyAmendments,
+    #[msg("Result data exceeds the maximum verifiable size")]
+    ResultDataTooLarge,
+    #[msg("Agent key is banned from the swarm")]
+    AgentBanned,
+    #[msg("Max participants must be between min participants and 10")]
+    InvalidParticipantCap,
+    #[msg("Coordination has reached its participant cap")]
+    CoordinationFull,
+    #[msg("Too many agents in one batch")]
+    TooManyAgents,
+    #[msg("Only fully successful executed coordinations pay rewards")]
+    NotRewardable,
+    #[msg("Agent already claimed this coordination's reward")]
+    AlreadyRewarded,
+    #[msg("Threat is already linked to this coordination")]
+    ThreatAlreadyLinked,
+    #[msg("Coordination already has the maximum related threats")]
+    TooManyRelatedThreats,
+    #[msg("Agent is already observing this coordination")]
+    AlreadyObserving,
+    #[msg("Coordination has the maximum number of observers")]
+    TooManyObservers,
+    #[msg("No forfeited bonds to sweep")]
+    NoForfeitedBonds,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coordination(join_weights: &[u8]) -> Coordination {
+        Coordination {
+            coordination_id: 0,
+            threat_id: 1,
+            related_threat_ids: vec![],
+            initiator: Pubkey::new_unique(),
+            required_capabilities: vec![Capability::ThreatDetection],
+            action_plan: String::new(),
+            amendment_count: 0,
+            urgency: Urgency::High,
+            priority_score: 0,
+            status: CoordinationStatus::Pending,
+            participating_agents: join_weights.iter().map(|_| Pubkey::new_unique()).collect(),
+            contributed_capabilities: vec![Capability::ThreatDetection; join_weights.len()],
+            join_weights: join_weights.to_vec(),
+            require_full_capability_coverage: false,
+            coordination_coverage_score: 0,
+            response_latency_seconds: 0,
+            min_participants: 1,
+            max_participants: 10,
+            voters: vec![],
+            vote_weights: vec![],
+            vote_record: vec![],
+            votes_for: 0,
+            votes_against: 0,
+            weighted_for: 0,
+            weighted_against: 0,
+            total_reputation_weight: join_weights.iter().map(|w| *w as u64).sum(),
+            initiated_at: 0,
+            deadline: 0,
+            executed_at: None,
+            result_hash: None,
+            comment_count: 0,
+            cancel_reason: String::new(),
+            failure_reason: String::new(),
+            callback: None,
+            callback_invoked: false,
+            executed_by: Pubkey::default(),
+            outcome: None,
+            rewarded_agents: vec![],
+            observers: vec![],
+            bond: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn weighted_vote_uses_join_time_weights() {
+        let mut coordination = coordination(&[80, 10, 10]);
+
+        tally_vote(&mut coordination, 0, true).unwrap();
+        tally_vote(&mut coordination, 1, false).unwrap();
+        tally_vote(&mut coordination, 2, false).unwrap();
+
+        assert_eq!((coordination.votes_for, coordination.votes_against), (1, 2));
+        assert_eq!(coordination.weighted_for, 80);
+        assert_eq!(coordination.weighted_against, 20);
+        assert_eq!(
+            coordination.vote_weights[0],
+            VoteWeight {
+                voter: coordination.participating_agents[0],
+                weight: 80,
+            }
+        );
+        // One heavy participant outweighs a headcount majority
+        assert!(weighted_majority(
+            coordination.weighted_for,
+            coordination.total_reputation_weight
+        ));
+    }
+
+    #[test]
+    fn weighted_majority_is_strict() {
+        assert!(!weighted_majority(50, 100));
+        assert!(weighted_majority(51, 100));
+        assert!(!weighted_majority(0, 0));
+    }
+
+    #[test]
+    fn releasing_active_urgency_saturates() {
+        let mut swarm = SwarmRegistry {
+            authority: Pubkey::new_unique(),
+            total_agents: 0,
+            type_counts: [0; 10],
+            active_coordinations: 1,
+            active_low: 0,
+            active_medium: 0,
+            active_high: 1,
+            active_critical: 0,
+            total_response_latency: 0,
+            executed_count: 0,
+            total_coordinations: 1,
+            capability_prerequisites: [0; 10],
+            pending_authority: None,
+            paused: false,
+            forfeited_bonds: 0,
+            bump: 0,
+        };
+
+        release_active_urgency(&mut swarm, Urgency::High);
+        release_active_urgency(&mut swarm, Urgency::High);
+
+        assert_eq!(swarm.active_high, 0);
+    }
+
+    #[test]
+    fn slash_amount_handles_large_stakes() {
+        assert_eq!(slash_amount(1_000, 50), 500);
+        assert_eq!(slash_amount(999, 0), 0);
+        assert_eq!(slash_amount(u64::MAX, 100), u64::MAX);
+        assert_eq!(slash_amount(u64::MAX, 50), u64::MAX / 2);
+    }
+
+    #[test]
+    fn crisis_index_blends_pressures() {
+        assert_eq!(threat_pressure(0, 0), 0);
+        // Five live threats averaging 80 are half way to saturation
+        assert_eq!(threat_pressure(5, 400), 40);
+        assert_eq!(threat_pressure(30, 2_400), 80);
+        assert_eq!(urgency_pressure([0, 0, 0, 0]), 0);
+        assert_eq!(urgency_pressure([1, 0, 0, 1]), 62);
+        assert_eq!(crisis_index(40, 62, 60, 40), 48);
+        assert_eq!(crisis_index(100, 100, 60, 40), 100);
+    }
+
+    fn agent() -> AgentRegistration {
+        AgentRegistration {
+            agent_id: Pubkey::new_unique(),
+            agent_type: AgentType::Sentinel,
+            capabilities: vec![Capability::ThreatDetection],
+            registered_at: 0,
+            last_active: 0,
+            active: false,
+            total_actions: 0,
+            successful_actions: 0,
+            coordinations_joined: 0,
+            coordinations_executed: 0,
+            reputation_score: 50,
+            staked: 0,
+            delegate: None,
+            deactivated_by: None,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn authority_deactivation_needs_authority_to_reactivate() {
+        let swarm_authority = Pubkey::new_unique();
+        let mut agent = agent();
+        agent.deactivated_by = Some(swarm_authority);
+
+        assert!(!can_reactivate(&agent, agent.agent_id, swarm_authority));
+        assert!(can_reactivate(&agent, swarm_authority, swarm_authority));
+    }
+
+    #[test]
+    fn self_deactivated_agent_may_reactivate() {
+        let swarm_authority = Pubkey::new_unique();
+        let mut agent = agent();
+        agent.deactivated_by = Some(agent.agent_id);
+
+        assert!(can_reactivate(&agent, agent.agent_id, swarm_authority));
+        assert!(!can_reactivate(&agent, Pubkey::new_unique(), swarm_authority));
+    }
+
+    #[test]
+    fn confirmation_reward_reports_capped_delta() {
+        assert_eq!(reputation_delta(90, 5, false), (95, 5));
+        assert_eq!(reputation_delta(98, 5, false), (100, 2));
+        assert_eq!(reputation_delta(100, 5, false), (100, 0));
+        // Clawing back exactly what was applied restores the score
+        assert_eq!(reputation_delta(100, 2, true), (98, 2));
+        assert_eq!(reputation_delta(3, 5, true), (0, 3));
+    }
+
+    fn error_code(err: Error) -> u32 {
+        match err {
+            Error::AnchorError(e) => e.error_code_number,
+            Error::ProgramError(e) => panic!("unexpected program error: {e}"),
+        }
+    }
+
+    #[test]
+    fn only_participants_execute() {
+        let mut coordination = coordination(&[50, 50]);
+        coordination.status = CoordinationStatus::Approved;
+        let participant = coordination.participating_agents[0];
+
+        assert!(ensure_executable(&coordination, &participant).is_ok());
+        let err = ensure_executable(&coordination, &Pubkey::new_unique()).unwrap_err();
+        assert_eq!(error_code(err), u32::from(ErrorCode::NotParticipant));
+    }
+
+    fn info<'a>(
+        key: &'a Pubkey,
+        lamports: &'a mut u64,
+        data: &'a mut [u8],
+        owner: &'a Pubkey,
+    ) -> AccountInfo<'a> {
+        AccountInfo::new(key, false, true, lamports, data, owner, false, 0)
+    }
+
+    fn swarm() -> SwarmRegistry {
+        SwarmRegistry {
+            authority: Pubkey::new_unique(),
+            total_agents: 0,
+            type_counts: [0; 10],
+            active_coordinations: 0,
+            active_low: 0,
+            active_medium: 0,
+            active_high: 0,
+            active_critical: 0,
+            total_response_latency: 0,
+            executed_count: 0,
+            total_coordinations: 0,
+            capability_prerequisites: [0; 10],
+            pending_authority: None,
+            paused: false,
+            forfeited_bonds: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn forfeited_bonds_accumulate_until_swept() {
+        let (mut coordination, mut swarm) = (coordination(&[50]), swarm());
+        coordination.bond = 1_000;
+        let (coord_key, swarm_key, treasury_key) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let (mut coord_lamports, mut swarm_lamports, mut treasury_lamports) = (5_000, 2_000, 0);
+        let (mut d1, mut d2, mut d3) = ([0u8; 0], [0u8; 0], [0u8; 0]);
+        let coord_info = info(&coord_key, &mut coord_lamports, &mut d1, &ID);
+        let swarm_info = info(&swarm_key, &mut swarm_lamports, &mut d2, &ID);
+        let treasury_info = info(&treasury_key, &mut treasury_lamports, &mut d3, &ID);
+
+        let forfeited = forfeit_bond(&mut coordination, &coord_info, &mut swarm, &swarm_info);
+        assert_eq!(forfeited.unwrap(), 1_000);
+        assert_eq!((coord_info.lamports(), swarm_info.lamports()), (4_000, 3_000));
+        assert_eq!(swarm.forfeited_bonds, 1_000);
+
+        assert_eq!(sweep_bonds(&mut swarm, &swarm_info, &treasury_info).unwrap(), 1_000);
+        // Only the forfeited bonds leave; the registry keeps its rent
+        assert_eq!((swarm_info.lamports(), treasury_info.lamports()), (2_000, 1_000));
+        let err = sweep_bonds(&mut swarm, &swarm_info, &treasury_info).unwrap_err();
+        assert_eq!(error_code(err), u32::from(ErrorCode::NoForfeitedBonds));
+    }
+
+    #[test]
+    fn bond_refunds_only_after_decision() {
+        let mut coordination = coordination(&[50]);
+        coordination.bond = 1_000;
+        let (coord_key, initiator_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (mut coord_lamports, mut initiator_lamports) = (5_000, 0);
+        let (mut d1, mut d2) = ([0u8; 0], [0u8; 0]);
+        let coord_info = info(&coord_key, &mut coord_lamports, &mut d1, &ID);
+        let initiator_info = info(&initiator_key, &mut initiator_lamports, &mut d2, &ID);
+
+        let err = refund_bond(&mut coordination, &coord_info, &initiator_info).unwrap_err();
+        assert_eq!(error_code(err), u32::from(ErrorCode::BondNotRefundable));
+
+        coordination.status = CoordinationStatus::Rejected;
+        assert_eq!(refund_bond(&mut coordination, &coord_info, &initiator_info).unwrap(), 1_000);
+        assert_eq!((coord_info.lamports(), initiator_info.lamports()), (4_000, 1_000));
+        let err = refund_bond(&mut coordination, &coord_info, &initiator_info).unwrap_err();
+        assert_eq!(error_code(err), u32::from(ErrorCode::NoBond));
+    }
 }
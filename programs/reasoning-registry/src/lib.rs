@@ -1,85 +1,96 @@
+#![allow(clippy::too_many_arguments)] // Anchor instruction handlers take their args positionally
+
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::hash::hashv;
+use agent_coordinator::AgentRegistration;
+use threat_intelligence::program::ThreatIntelligence;
+use threat_intelligence::{TargetKind, ThreatCounter, ThreatType};
 
 declare_id!("87CGxPABDUwvSRzByXeMcmZ5Qo8B6225z2q8D8VkxUjt"); // Will be updated after first build
 
+/// Accuracy penalty when an agent exhausts its reveal attempts
+pub const REVEAL_FAILURE_PENALTY: u8 = 10;
+
+/// Accuracy change per auditor judgement
+pub const ACCURACY_ADJUSTMENT: u8 = 5;
+
+/// Distinct disputes after which a reasoning commit is contested
+pub const CONTEST_THRESHOLD: u8 = 3;
+
+/// Reveal promptness scores: within the first half of the window, later, or past the deadline
+pub const PROMPT_REVEAL_SCORE: u8 = 100;
+pub const LATE_REVEAL_SCORE: u8 = 50;
+pub const OVERDUE_REVEAL_SCORE: u8 = 0;
+
+/// How long after committing an agent may still amend its reasoning hash (5 minutes)
+pub const COMMIT_AMEND_WINDOW_SECONDS: i64 = 5 * 60;
+
+/// Longest reveal window a committer may choose, so the overdue timeout always applies (7 days)
+pub const MAX_REVEAL_WINDOW_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+/// How long a revealed commit is retained before its rent can be reclaimed (30 days)
+pub const REASONING_RETENTION_SECONDS: i64 = 30 * 24 * 60 * 60;
+
 #[program]
 pub mod reasoning_registry {
     use super::*;
 
     /// Commit reasoning hash on-chain BEFORE taking any action
     /// This ensures transparency and prevents post-hoc reasoning manipulation
+    /// The hash is `hash(salt || reasoning_text)`; the salt stays off-chain until reveal
     pub fn commit_reasoning(
         ctx: Context<CommitReasoning>,
         agent_id: Pubkey,
         reasoning_hash: [u8; 32],
         threat_id: u64,
-        action_type: ActionType,
+        action_plan: Vec<ActionType>,
+        max_reveal_attempts: u8,
+        reveal_window_seconds: i64,
+        delegate: Option<Pubkey>,
     ) -> Result<()> {
-        let reasoning_commit = &mut ctx.accounts.reasoning_commit;
-        let clock = Clock::get()?;
-        
-        reasoning_commit.agent_id = agent_id;
-        reasoning_commit.reasoning_hash = reasoning_hash;
-        reasoning_commit.threat_id = threat_id;
-        reasoning_commit.action_type = action_type;
-        reasoning_commit.commit_timestamp = clock.unix_timestamp;
-        reasoning_commit.revealed = false;
-        reasoning_commit.reveal_timestamp = None;
-        reasoning_commit.reasoning_text = String::new();
-        reasoning_commit.bump = ctx.bumps.reasoning_commit;
-
-        emit!(ReasoningCommitted {
+        record_commit(
+            &mut ctx.accounts.reasoning_commit,
+            &mut ctx.accounts.agent_registry,
+            ctx.accounts.authority.key(),
             agent_id,
-            threat_id,
             reasoning_hash,
-            action_type,
-            timestamp: clock.unix_timestamp,
-        });
+            threat_id,
+            action_plan,
+            max_reveal_attempts,
+            reveal_window_seconds,
+            delegate,
+            ctx.bumps.reasoning_commit,
+        )?;
 
-        msg!(
-            "Agent {} committed reasoning for threat {} with action {:?}",
-            agent_id,
+        index_commit(
+            &mut ctx.accounts.threat_reasoning_count,
+            &mut ctx.accounts.threat_reasoning_entry,
             threat_id,
-            action_type
+            agent_id,
+            ctx.bumps.threat_reasoning_count,
+            ctx.bumps.threat_reasoning_entry,
         );
         Ok(())
     }
 
     /// Reveal the full reasoning text after action is taken
-    /// Verifies the hash matches what was committed
+    /// Verifies the hash matches what was committed; a mismatch is recorded
+    /// as a failed attempt rather than reverted
     pub fn reveal_reasoning(
         ctx: Context<RevealReasoning>,
         reasoning_text: String,
+        salt: [u8; 32],
     ) -> Result<()> {
         let reasoning_commit = &mut ctx.accounts.reasoning_commit;
+        let agent_registry = &mut ctx.accounts.agent_registry;
         let clock = Clock::get()?;
 
-        // Cannot reveal twice
-        require!(!reasoning_commit.revealed, ErrorCode::AlreadyRevealed);
-
-        // Verify hash matches
-        let computed_hash = hash(reasoning_text.as_bytes());
-        require!(
-            computed_hash.to_bytes() == reasoning_commit.reasoning_hash,
-            ErrorCode::HashMismatch
-        );
-
-        reasoning_commit.reasoning_text = reasoning_text.clone();
-        reasoning_commit.revealed = true;
-        reasoning_commit.reveal_timestamp = Some(clock.unix_timestamp);
-
-        emit!(ReasoningRevealed {
-            agent_id: reasoning_commit.agent_id,
-            threat_id: reasoning_commit.threat_id,
-            reasoning_text,
-            timestamp: clock.unix_timestamp,
-        });
-
-        msg!(
-            "Reasoning revealed and verified for threat {}",
-            reasoning_commit.threat_id
-        );
+        if reveal_commit(reasoning_commit, agent_registry, reasoning_text, salt, clock.unix_timestamp)? {
+            msg!(
+                "Reasoning revealed and verified for threat {}",
+                reasoning_commit.threat_id
+            );
+        }
         Ok(())
     }
 
@@ -90,8 +101,8 @@ pub mod reasoning_registry {
         // Must be revealed first
         require!(reasoning_commit.revealed, ErrorCode::NotRevealed);
 
-        let computed_hash = hash(reasoning_commit.reasoning_text.as_bytes());
-        let is_valid = computed_hash.to_bytes() == reasoning_commit.reasoning_hash;
+        let computed_hash = salted_hash(&reasoning_commit.salt, &reasoning_commit.reasoning_text);
+        let is_valid = computed_hash == reasoning_commit.reasoning_hash;
 
         emit!(ReasoningVerified {
             agent_id: reasoning_commit.agent_id,
@@ -110,6 +121,10 @@ pub mod reasoning_registry {
             total_commits: agent_registry.total_commits,
             total_reveals: agent_registry.total_reveals,
             accuracy_score: agent_registry.accuracy_score,
+            average_promptness: agent_registry
+                .promptness_sum
+                .checked_div(agent_registry.promptness_count)
+                .unwrap_or(0) as u8,
         })
     }
 
@@ -123,11 +138,314 @@ pub mod reasoning_registry {
         registry.total_commits = 0;
         registry.total_reveals = 0;
         registry.accuracy_score = 100; // Start at 100%, decreases on false positives
+        registry.promptness_sum = 0;
+        registry.promptness_count = 0;
         registry.bump = ctx.bumps.agent_registry;
 
         msg!("Initialized registry for agent {}", agent_id);
         Ok(())
     }
+
+    /// Reveal and verify in one atomic step so a reveal never sits unverified
+    /// The hash is checked during the reveal, so its result is the verification
+    pub fn reveal_and_verify(
+        ctx: Context<RevealAndVerify>,
+        reasoning_text: String,
+        salt: [u8; 32],
+    ) -> Result<bool> {
+        let reasoning_commit = &mut ctx.accounts.reasoning_commit;
+        let agent_registry = &mut ctx.accounts.agent_registry;
+        let clock = Clock::get()?;
+
+        let is_valid =
+            reveal_commit(reasoning_commit, agent_registry, reasoning_text, salt, clock.unix_timestamp)?;
+
+        emit!(ReasoningVerified {
+            agent_id: reasoning_commit.agent_id,
+            threat_id: reasoning_commit.threat_id,
+            is_valid,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Reasoning for threat {} verified: {}",
+            reasoning_commit.threat_id,
+            is_valid
+        );
+        Ok(is_valid)
+    }
+
+    /// Flag a commit whose reveal window lapsed without a reveal. Callable by anyone;
+    /// the agent's accuracy is penalized as for exhausted reveal attempts
+    pub fn mark_reveal_overdue(ctx: Context<MarkRevealOverdue>) -> Result<()> {
+        let reasoning_commit = &mut ctx.accounts.reasoning_commit;
+        let agent_registry = &mut ctx.accounts.agent_registry;
+        let clock = Clock::get()?;
+
+        require!(!reasoning_commit.revealed, ErrorCode::AlreadyRevealed);
+        require!(!reasoning_commit.reveal_overdue, ErrorCode::AlreadyOverdue);
+        // Exhausted reveal attempts were already penalized once
+        require!(!reasoning_commit.reveal_failed, ErrorCode::RevealLocked);
+        require!(
+            clock.unix_timestamp > reasoning_commit.reveal_deadline,
+            ErrorCode::RevealWindowOpen
+        );
+
+        reasoning_commit.reveal_overdue = true;
+        reasoning_commit.reveal_promptness = OVERDUE_REVEAL_SCORE;
+        record_promptness(agent_registry, OVERDUE_REVEAL_SCORE);
+        agent_registry.accuracy_score = agent_registry
+            .accuracy_score
+            .saturating_sub(REVEAL_FAILURE_PENALTY);
+
+        emit!(RevealOverdue {
+            agent_id: reasoning_commit.agent_id,
+            threat_id: reasoning_commit.threat_id,
+            reveal_deadline: reasoning_commit.reveal_deadline,
+            new_accuracy_score: agent_registry.accuracy_score,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Reveal overdue for threat {} by agent {}",
+            reasoning_commit.threat_id,
+            reasoning_commit.agent_id
+        );
+        Ok(())
+    }
+
+    /// Set up the registry config naming the auditor who judges reasoning outcomes
+    pub fn initialize_registry_config(
+        ctx: Context<InitializeRegistryConfig>,
+        auditor: Pubkey,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.registry_config;
+        config.authority = ctx.accounts.authority.key();
+        config.auditor = auditor;
+        config.bump = ctx.bumps.registry_config;
+
+        msg!("Registry config initialized with auditor {}", auditor);
+        Ok(())
+    }
+
+    /// Auditor judgement on an agent's reasoning: nudges accuracy up when it was
+    /// correct and down when it was not
+    pub fn adjust_accuracy(ctx: Context<AdjustAccuracy>, was_correct: bool) -> Result<()> {
+        let agent_registry = &mut ctx.accounts.agent_registry;
+        let old_score = agent_registry.accuracy_score;
+
+        agent_registry.accuracy_score = if was_correct {
+            std::cmp::min(100, old_score.saturating_add(ACCURACY_ADJUSTMENT))
+        } else {
+            old_score.saturating_sub(ACCURACY_ADJUSTMENT)
+        };
+
+        emit!(AccuracyAdjusted {
+            agent_id: agent_registry.agent_id,
+            was_correct,
+            old_score,
+            new_score: agent_registry.accuracy_score,
+            auditor: ctx.accounts.auditor.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!(
+            "Accuracy for agent {} adjusted to {}",
+            agent_registry.agent_id,
+            agent_registry.accuracy_score
+        );
+        Ok(())
+    }
+
+    /// Close a revealed commit after its retention period, returning rent to the
+    /// authority that created it. The event keeps the record in the logs
+    pub fn close_reasoning_commit(ctx: Context<CloseReasoningCommit>) -> Result<()> {
+        let reasoning_commit = &ctx.accounts.reasoning_commit;
+        let clock = Clock::get()?;
+
+        require!(reasoning_commit.revealed, ErrorCode::NotRevealed);
+        require!(
+            clock.unix_timestamp >= reasoning_commit.close_after,
+            ErrorCode::RetentionPeriodActive
+        );
+
+        emit!(ReasoningCommitClosed {
+            agent_id: reasoning_commit.agent_id,
+            threat_id: reasoning_commit.threat_id,
+            reasoning_hash: reasoning_commit.reasoning_hash,
+            closed_by: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Closed reasoning commit for threat {}",
+            reasoning_commit.threat_id
+        );
+        Ok(())
+    }
+
+    /// Flag revealed reasoning as bad-faith or incorrect
+    /// Three distinct active swarm agents disputing mark the commit as contested
+    pub fn dispute_reasoning(ctx: Context<DisputeReasoning>, dispute_reason: String) -> Result<()> {
+        require!(dispute_reason.len() <= 200, ErrorCode::DisputeReasonTooLong);
+
+        let reasoning_commit = &mut ctx.accounts.reasoning_commit;
+        let disputer = ctx.accounts.disputer.key();
+        let clock = Clock::get()?;
+
+        require!(reasoning_commit.revealed, ErrorCode::NotRevealed);
+        require!(
+            disputer != reasoning_commit.agent_id && disputer != reasoning_commit.authority,
+            ErrorCode::CannotDisputeOwn
+        );
+        require!(
+            !reasoning_commit.disputers.contains(&disputer),
+            ErrorCode::AlreadyDisputed
+        );
+        require!(
+            reasoning_commit.disputers.len() < 5,
+            ErrorCode::DisputeLimitReached
+        );
+
+        reasoning_commit.disputers.push(disputer);
+        reasoning_commit.dispute_count += 1;
+        reasoning_commit.disputed = true;
+
+        emit!(ReasoningDisputed {
+            agent_id: reasoning_commit.agent_id,
+            threat_id: reasoning_commit.threat_id,
+            disputer,
+            dispute_reason,
+            dispute_count: reasoning_commit.dispute_count,
+            timestamp: clock.unix_timestamp,
+        });
+
+        if reasoning_commit.dispute_count >= CONTEST_THRESHOLD && !reasoning_commit.contested {
+            reasoning_commit.contested = true;
+            emit!(ReasoningContested {
+                agent_id: reasoning_commit.agent_id,
+                threat_id: reasoning_commit.threat_id,
+                disputers: reasoning_commit.disputers.clone(),
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        msg!(
+            "Reasoning for threat {} disputed ({} disputes)",
+            reasoning_commit.threat_id,
+            reasoning_commit.dispute_count
+        );
+        Ok(())
+    }
+
+    /// Register a threat in threat-intelligence and commit reasoning for it in one
+    /// transaction, so the commit provably precedes any action on the threat
+    pub fn register_threat_and_commit(
+        ctx: Context<RegisterThreatAndCommit>,
+        threat_type: ThreatType,
+        severity: u8,
+        target_address: Option<Pubkey>,
+        target_kind: TargetKind,
+        description: String,
+        evidence_hash: [u8; 32],
+        evidence_uri: String,
+        auto_coordinate: bool,
+        agent_id: Pubkey,
+        reasoning_hash: [u8; 32],
+        action_plan: Vec<ActionType>,
+        max_reveal_attempts: u8,
+        reveal_window_seconds: i64,
+        delegate: Option<Pubkey>,
+    ) -> Result<()> {
+        // The commit PDA was derived from the counter before registration
+        let expected_threat_id = ctx.accounts.threat_counter.count;
+
+        let threat_id = threat_intelligence::cpi::register_threat(
+            CpiContext::new(
+                ctx.accounts.threat_program.to_account_info(),
+                threat_intelligence::cpi::accounts::RegisterThreat {
+                    threat: ctx.accounts.threat.to_account_info(),
+                    threat_counter: ctx.accounts.threat_counter.to_account_info(),
+                    threat_type_index: ctx.accounts.threat_type_index.to_account_info(),
+                    threat_of_type: ctx.accounts.threat_of_type.to_account_info(),
+                    rate_limit: ctx.accounts.rate_limit.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                },
+            ),
+            threat_type,
+            severity,
+            target_address,
+            target_kind,
+            description,
+            evidence_hash,
+            evidence_uri,
+            auto_coordinate,
+        )?
+        .get();
+        require!(threat_id == expected_threat_id, ErrorCode::ThreatIdMismatch);
+
+        record_commit(
+            &mut ctx.accounts.reasoning_commit,
+            &mut ctx.accounts.agent_registry,
+            ctx.accounts.authority.key(),
+            agent_id,
+            reasoning_hash,
+            threat_id,
+            action_plan,
+            max_reveal_attempts,
+            reveal_window_seconds,
+            delegate,
+            ctx.bumps.reasoning_commit,
+        )?;
+
+        index_commit(
+            &mut ctx.accounts.threat_reasoning_count,
+            &mut ctx.accounts.threat_reasoning_entry,
+            threat_id,
+            agent_id,
+            ctx.bumps.threat_reasoning_count,
+            ctx.bumps.threat_reasoning_entry,
+        );
+        Ok(())
+    }
+
+    /// Correct a reasoning hash shortly after committing, before any reveal attempt
+    pub fn amend_commit(ctx: Context<AmendCommit>, new_reasoning_hash: [u8; 32]) -> Result<()> {
+        let reasoning_commit = &mut ctx.accounts.reasoning_commit;
+        let clock = Clock::get()?;
+
+        // Once a reveal has been tried the committed hash must stay fixed
+        require!(
+            !reasoning_commit.revealed
+                && reasoning_commit.failed_reveal_attempts == 0
+                && clock.unix_timestamp
+                    <= reasoning_commit.commit_timestamp + COMMIT_AMEND_WINDOW_SECONDS,
+            ErrorCode::CommitLocked
+        );
+
+        reasoning_commit.reasoning_hash = new_reasoning_hash;
+        reasoning_commit.revision = reasoning_commit
+            .revision
+            .checked_add(1)
+            .ok_or(ErrorCode::CommitLocked)?;
+
+        emit!(CommitAmended {
+            agent_id: reasoning_commit.agent_id,
+            threat_id: reasoning_commit.threat_id,
+            reasoning_hash: new_reasoning_hash,
+            revision: reasoning_commit.revision,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Agent {} amended reasoning for threat {} (revision {})",
+            reasoning_commit.agent_id,
+            reasoning_commit.threat_id,
+            reasoning_commit.revision
+        );
+        Ok(())
+    }
 }
 
 // ============== ACCOUNTS ==============
@@ -144,6 +462,35 @@ pub struct CommitReasoning<'info> {
     )]
     pub reasoning_commit: Account<'info, ReasoningCommit>,
     
+    #[account(
+        mut,
+        seeds = [b"agent_registry", agent_id.as_ref()],
+        bump = agent_registry.bump
+    )]
+    pub agent_registry: Account<'info, AgentRegistry>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ThreatReasoningCount::INIT_SPACE,
+        seeds = [b"threat_reasoning_count", threat_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub threat_reasoning_count: Account<'info, ThreatReasoningCount>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ThreatReasoningEntry::INIT_SPACE,
+        seeds = [
+            b"threat_reasoning".as_ref(),
+            threat_id.to_le_bytes().as_ref(),
+            threat_reasoning_count.count.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub threat_reasoning_entry: Account<'info, ThreatReasoningEntry>,
+    
     #[account(mut)]
     pub authority: Signer<'info>,
     
@@ -155,12 +502,22 @@ pub struct RevealReasoning<'info> {
     #[account(
         mut,
         has_one = agent_id @ ErrorCode::UnauthorizedAgent,
+        constraint = authority.key() == reasoning_commit.agent_id
+            || reasoning_commit.delegate == Some(authority.key()) @ ErrorCode::UnauthorizedAgent,
     )]
     pub reasoning_commit: Account<'info, ReasoningCommit>,
     
+    #[account(
+        mut,
+        seeds = [b"agent_registry", agent_id.key().as_ref()],
+        bump = agent_registry.bump
+    )]
+    pub agent_registry: Account<'info, AgentRegistry>,
+    
     /// CHECK: Verified via has_one constraint
     pub agent_id: UncheckedAccount<'info>,
     
+    /// The committing agent or its delegate
     pub authority: Signer<'info>,
 }
 
@@ -192,20 +549,433 @@ pub struct GetAgentStats<'info> {
     pub agent_registry: Account<'info, AgentRegistry>,
 }
 
+#[derive(Accounts)]
+pub struct RevealAndVerify<'info> {
+    #[account(
+        mut,
+        has_one = agent_id @ ErrorCode::UnauthorizedAgent,
+        constraint = authority.key() == reasoning_commit.agent_id
+            || reasoning_commit.delegate == Some(authority.key()) @ ErrorCode::UnauthorizedAgent,
+    )]
+    pub reasoning_commit: Account<'info, ReasoningCommit>,
+    
+    #[account(
+        mut,
+        seeds = [b"agent_registry", agent_id.key().as_ref()],
+        bump = agent_registry.bump
+    )]
+    pub agent_registry: Account<'info, AgentRegistry>,
+    
+    /// CHECK: Verified via has_one constraint
+    pub agent_id: UncheckedAccount<'info>,
+    
+    /// The committing agent or its delegate
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MarkRevealOverdue<'info> {
+    #[account(
+        mut,
+        has_one = agent_id @ ErrorCode::UnauthorizedAgent,
+    )]
+    pub reasoning_commit: Account<'info, ReasoningCommit>,
+    
+    #[account(
+        mut,
+        seeds = [b"agent_registry", agent_id.key().as_ref()],
+        bump = agent_registry.bump
+    )]
+    pub agent_registry: Account<'info, AgentRegistry>,
+    
+    /// CHECK: Verified via has_one constraint
+    pub agent_id: UncheckedAccount<'info>,
+    
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeRegistryConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + RegistryConfig::INIT_SPACE,
+        seeds = [b"registry_config"],
+        bump
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AdjustAccuracy<'info> {
+    #[account(mut)]
+    pub agent_registry: Account<'info, AgentRegistry>,
+    
+    #[account(
+        seeds = [b"registry_config"],
+        bump = registry_config.bump,
+        has_one = auditor @ ErrorCode::UnauthorizedAuditor
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+    
+    pub auditor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseReasoningCommit<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::UnauthorizedAgent,
+        close = authority
+    )]
+    pub reasoning_commit: Account<'info, ReasoningCommit>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DisputeReasoning<'info> {
+    #[account(mut)]
+    pub reasoning_commit: Account<'info, ReasoningCommit>,
+    
+    /// Only active swarm agents may dispute; reasoning registries are open to anyone
+    #[account(
+        seeds = [b"agent", disputer.key().as_ref()],
+        bump = disputer_registration.bump,
+        seeds::program = agent_coordinator::ID,
+        constraint = disputer_registration.active @ ErrorCode::DisputerInactive
+    )]
+    pub disputer_registration: Account<'info, AgentRegistration>,
+    
+    pub disputer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    threat_type: ThreatType,
+    severity: u8,
+    target_address: Option<Pubkey>,
+    target_kind: TargetKind,
+    description: String,
+    evidence_hash: [u8; 32],
+    evidence_uri: String,
+    auto_coordinate: bool,
+    agent_id: Pubkey,
+)]
+pub struct RegisterThreatAndCommit<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ReasoningCommit::INIT_SPACE,
+        seeds = [b"reasoning", agent_id.as_ref(), &threat_counter.count.to_le_bytes()],
+        bump
+    )]
+    pub reasoning_commit: Account<'info, ReasoningCommit>,
+    
+    #[account(
+        mut,
+        seeds = [b"agent_registry", agent_id.as_ref()],
+        bump = agent_registry.bump
+    )]
+    pub agent_registry: Account<'info, AgentRegistry>,
+    
+    /// CHECK: Threat PDA created by threat-intelligence during the CPI
+    #[account(mut)]
+    pub threat: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"threat_counter"],
+        bump = threat_counter.bump,
+        seeds::program = threat_intelligence::ID
+    )]
+    pub threat_counter: Account<'info, ThreatCounter>,
+    
+    /// CHECK: Per-type counter validated by threat-intelligence during the CPI
+    #[account(mut)]
+    pub threat_type_index: UncheckedAccount<'info>,
+    
+    /// CHECK: Per-type index entry created by threat-intelligence during the CPI
+    #[account(mut)]
+    pub threat_of_type: UncheckedAccount<'info>,
+    
+    /// CHECK: Registration rate limit validated by threat-intelligence during the CPI
+    #[account(mut)]
+    pub rate_limit: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ThreatReasoningCount::INIT_SPACE,
+        seeds = [b"threat_reasoning_count", threat_counter.count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub threat_reasoning_count: Account<'info, ThreatReasoningCount>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ThreatReasoningEntry::INIT_SPACE,
+        seeds = [
+            b"threat_reasoning".as_ref(),
+            threat_counter.count.to_le_bytes().as_ref(),
+            threat_reasoning_count.count.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub threat_reasoning_entry: Account<'info, ThreatReasoningEntry>,
+    
+    pub threat_program: Program<'info, ThreatIntelligence>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AmendCommit<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == reasoning_commit.agent_id
+            || reasoning_commit.delegate == Some(authority.key()) @ ErrorCode::UnauthorizedAgent,
+    )]
+    pub reasoning_commit: Account<'info, ReasoningCommit>,
+    
+    /// The committing agent or its delegate
+    pub authority: Signer<'info>,
+}
+
+// ============== HELPERS ==============
+
+/// Validate and write a new reasoning commit, counting it toward the agent's stats
+fn record_commit(
+    reasoning_commit: &mut ReasoningCommit,
+    agent_registry: &mut AgentRegistry,
+    authority: Pubkey,
+    agent_id: Pubkey,
+    reasoning_hash: [u8; 32],
+    threat_id: u64,
+    action_plan: Vec<ActionType>,
+    max_reveal_attempts: u8,
+    reveal_window_seconds: i64,
+    delegate: Option<Pubkey>,
+    bump: u8,
+) -> Result<()> {
+    // Only the agent itself may open a commit (and name its delegate), so nobody
+    // can squat an agent's commit PDA for a threat
+    require_keys_eq!(authority, agent_id, ErrorCode::UnauthorizedAgent);
+    require!(
+        (1..=10).contains(&max_reveal_attempts),
+        ErrorCode::InvalidRevealAttempts
+    );
+    require!(
+        (1..=MAX_REVEAL_WINDOW_SECONDS).contains(&reveal_window_seconds),
+        ErrorCode::InvalidRevealWindow
+    );
+    require!(!action_plan.is_empty(), ErrorCode::EmptyActionPlan);
+    require!(action_plan.len() <= 6, ErrorCode::ActionPlanTooLong);
+    require!(
+        action_plan.windows(2).all(|pair| pair[0] != pair[1]),
+        ErrorCode::RepeatedAction
+    );
+
+    let clock = Clock::get()?;
+    
+    reasoning_commit.agent_id = agent_id;
+    reasoning_commit.authority = authority;
+    reasoning_commit.delegate = delegate;
+    reasoning_commit.reasoning_hash = reasoning_hash;
+    reasoning_commit.threat_id = threat_id;
+    reasoning_commit.action_plan = action_plan.clone();
+    reasoning_commit.commit_timestamp = clock.unix_timestamp;
+    reasoning_commit.revealed = false;
+    reasoning_commit.reveal_timestamp = None;
+    reasoning_commit.reasoning_text = String::new();
+    reasoning_commit.salt = [0u8; 32];
+    reasoning_commit.failed_reveal_attempts = 0;
+    reasoning_commit.max_reveal_attempts = max_reveal_attempts;
+    reasoning_commit.reveal_failed = false;
+    reasoning_commit.reveal_deadline = clock
+        .unix_timestamp
+        .checked_add(reveal_window_seconds)
+        .ok_or(ErrorCode::InvalidRevealWindow)?;
+    reasoning_commit.reveal_overdue = false;
+    reasoning_commit.close_after = 0;
+    reasoning_commit.disputed = false;
+    reasoning_commit.dispute_count = 0;
+    reasoning_commit.disputers = vec![];
+    reasoning_commit.contested = false;
+    reasoning_commit.revision = 0;
+    reasoning_commit.reveal_promptness = 0;
+    reasoning_commit.bump = bump;
+
+    agent_registry.total_commits += 1;
+
+    emit!(ReasoningCommitted {
+        agent_id,
+        threat_id,
+        reasoning_hash,
+        action_plan: action_plan.clone(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Agent {} committed reasoning for threat {} with actions {:?}",
+        agent_id,
+        threat_id,
+        action_plan
+    );
+    Ok(())
+}
+
+/// Append an agent's commit to its threat's index so commits can be enumerated by threat
+fn index_commit(
+    threat_reasoning_count: &mut ThreatReasoningCount,
+    threat_reasoning_entry: &mut ThreatReasoningEntry,
+    threat_id: u64,
+    agent_id: Pubkey,
+    count_bump: u8,
+    entry_bump: u8,
+) {
+    if threat_reasoning_count.count == 0 {
+        threat_reasoning_count.threat_id = threat_id;
+        threat_reasoning_count.bump = count_bump;
+    }
+
+    threat_reasoning_entry.threat_id = threat_id;
+    threat_reasoning_entry.local_index = threat_reasoning_count.count;
+    threat_reasoning_entry.agent_id = agent_id;
+    threat_reasoning_entry.bump = entry_bump;
+
+    threat_reasoning_count.count += 1;
+}
+
+/// Classify how quickly a commit was revealed relative to its reveal window
+fn reveal_promptness(reasoning_commit: &ReasoningCommit, reveal_timestamp: i64) -> u8 {
+    let elapsed = reveal_timestamp - reasoning_commit.commit_timestamp;
+    let window = reasoning_commit.reveal_deadline - reasoning_commit.commit_timestamp;
+
+    if elapsed <= window / 2 {
+        PROMPT_REVEAL_SCORE
+    } else if elapsed <= window {
+        LATE_REVEAL_SCORE
+    } else {
+        OVERDUE_REVEAL_SCORE
+    }
+}
+
+/// Fold a promptness score into the agent's running average
+fn record_promptness(agent_registry: &mut AgentRegistry, score: u8) {
+    agent_registry.promptness_sum += score as u64;
+    agent_registry.promptness_count += 1;
+}
+
+/// Commitment hash over `salt || reasoning_text`
+fn salted_hash(salt: &[u8; 32], reasoning_text: &str) -> [u8; 32] {
+    hashv(&[salt.as_ref(), reasoning_text.as_bytes()]).to_bytes()
+}
+
+/// Check the revealed text against the committed hash and record the reveal.
+/// Returns false on a mismatch, which counts against the commit's attempt cap;
+/// once the cap is hit the commit is locked and the agent penalized
+fn reveal_commit(
+    reasoning_commit: &mut ReasoningCommit,
+    agent_registry: &mut AgentRegistry,
+    reasoning_text: String,
+    salt: [u8; 32],
+    timestamp: i64,
+) -> Result<bool> {
+    // Cannot reveal twice
+    require!(!reasoning_commit.revealed, ErrorCode::AlreadyRevealed);
+    require!(!reasoning_commit.reveal_failed, ErrorCode::RevealLocked);
+    require!(
+        timestamp <= reasoning_commit.reveal_deadline,
+        ErrorCode::RevealWindowExpired
+    );
+
+    // Verify hash matches
+    if salted_hash(&salt, &reasoning_text) != reasoning_commit.reasoning_hash {
+        reasoning_commit.failed_reveal_attempts += 1;
+        msg!(
+            "Hash mismatch on reveal attempt {}/{}",
+            reasoning_commit.failed_reveal_attempts,
+            reasoning_commit.max_reveal_attempts
+        );
+
+        if reasoning_commit.failed_reveal_attempts >= reasoning_commit.max_reveal_attempts {
+            reasoning_commit.reveal_failed = true;
+            agent_registry.accuracy_score = agent_registry
+                .accuracy_score
+                .saturating_sub(REVEAL_FAILURE_PENALTY);
+
+            emit!(RevealAttemptsExhausted {
+                agent_id: reasoning_commit.agent_id,
+                threat_id: reasoning_commit.threat_id,
+                attempts: reasoning_commit.failed_reveal_attempts,
+                new_accuracy_score: agent_registry.accuracy_score,
+                timestamp,
+            });
+        }
+        return Ok(false);
+    }
+
+    reasoning_commit.reasoning_text = reasoning_text.clone();
+    reasoning_commit.salt = salt;
+    reasoning_commit.revealed = true;
+    reasoning_commit.reveal_timestamp = Some(timestamp);
+    reasoning_commit.close_after = timestamp.saturating_add(REASONING_RETENTION_SECONDS);
+    reasoning_commit.reveal_promptness = reveal_promptness(reasoning_commit, timestamp);
+    agent_registry.total_reveals += 1;
+    record_promptness(agent_registry, reasoning_commit.reveal_promptness);
+
+    emit!(ReasoningRevealed {
+        agent_id: reasoning_commit.agent_id,
+        threat_id: reasoning_commit.threat_id,
+        reasoning_text,
+        timestamp,
+    });
+
+    Ok(true)
+}
+
 // ============== STATE ==============
 
 #[account]
 #[derive(InitSpace)]
 pub struct ReasoningCommit {
     pub agent_id: Pubkey,
+    pub authority: Pubkey, // payer; reclaims rent on close
+    pub delegate: Option<Pubkey>, // may reveal on the agent's behalf
     pub reasoning_hash: [u8; 32],
     pub threat_id: u64,
-    pub action_type: ActionType,
+    #[max_len(6)]
+    pub action_plan: Vec<ActionType>, // ordered playbook of actions
     pub commit_timestamp: i64,
     pub revealed: bool,
     pub reveal_timestamp: Option<i64>,
     #[max_len(2000)]
     pub reasoning_text: String,
+    pub salt: [u8; 32], // zero until revealed
+    pub failed_reveal_attempts: u8,
+    pub max_reveal_attempts: u8,
+    pub reveal_failed: bool, // locked after exhausting reveal attempts
+    pub reveal_deadline: i64,
+    pub reveal_overdue: bool, // flagged once the deadline passed unrevealed
+    pub close_after: i64, // set on reveal; 0 until then
+    pub disputed: bool,
+    pub dispute_count: u8,
+    #[max_len(5)]
+    pub disputers: Vec<Pubkey>,
+    pub contested: bool, // disputed by CONTEST_THRESHOLD distinct agents
+    pub revision: u8, // times the hash was amended within the amend window
+    pub reveal_promptness: u8, // PROMPT/LATE/OVERDUE_REVEAL_SCORE; 0 until revealed or overdue
     pub bump: u8,
 }
 
@@ -216,6 +986,35 @@ pub struct AgentRegistry {
     pub total_commits: u64,
     pub total_reveals: u64,
     pub accuracy_score: u8, // 0-100
+    pub promptness_sum: u64, // summed reveal promptness scores
+    pub promptness_count: u64,
+    pub bump: u8,
+}
+
+/// Number of reasoning commits made for a threat, across all agents
+#[account]
+#[derive(InitSpace)]
+pub struct ThreatReasoningCount {
+    pub threat_id: u64,
+    pub count: u64,
+    pub bump: u8,
+}
+
+/// Maps a threat-local commit index to the committing agent
+#[account]
+#[derive(InitSpace)]
+pub struct ThreatReasoningEntry {
+    pub threat_id: u64,
+    pub local_index: u64,
+    pub agent_id: Pubkey,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct RegistryConfig {
+    pub authority: Pubkey,
+    pub auditor: Pubkey, // may adjust agents' accuracy scores
     pub bump: u8,
 }
 
@@ -236,6 +1035,7 @@ pub struct AgentStats {
     pub total_commits: u64,
     pub total_reveals: u64,
     pub accuracy_score: u8,
+    pub average_promptness: u8, // 0-100, higher reveals sooner
 }
 
 // ============== EVENTS ==============
@@ -245,7 +1045,7 @@ pub struct ReasoningCommitted {
     pub agent_id: Pubkey,
     pub threat_id: u64,
     pub reasoning_hash: [u8; 32],
-    pub action_type: ActionType,
+    pub action_plan: Vec<ActionType>,
     pub timestamp: i64,
 }
 
@@ -265,6 +1065,70 @@ pub struct ReasoningVerified {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct RevealAttemptsExhausted {
+    pub agent_id: Pubkey,
+    pub threat_id: u64,
+    pub attempts: u8,
+    pub new_accuracy_score: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RevealOverdue {
+    pub agent_id: Pubkey,
+    pub threat_id: u64,
+    pub reveal_deadline: i64,
+    pub new_accuracy_score: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AccuracyAdjusted {
+    pub agent_id: Pubkey,
+    pub was_correct: bool,
+    pub old_score: u8,
+    pub new_score: u8,
+    pub auditor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReasoningCommitClosed {
+    pub agent_id: Pubkey,
+    pub threat_id: u64,
+    pub reasoning_hash: [u8; 32],
+    pub closed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReasoningDisputed {
+    pub agent_id: Pubkey,
+    pub threat_id: u64,
+    pub disputer: Pubkey,
+    pub dispute_reason: String,
+    pub dispute_count: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReasoningContested {
+    pub agent_id: Pubkey,
+    pub threat_id: u64,
+    pub disputers: Vec<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CommitAmended {
+    pub agent_id: Pubkey,
+    pub threat_id: u64,
+    pub reasoning_hash: [u8; 32],
+    pub revision: u8,
+    pub timestamp: i64,
+}
+
 // ============== ERRORS ==============
 
 #[error_code]
@@ -279,4 +1143,185 @@ pub enum ErrorCode {
     UnauthorizedAgent,
     #[msg("Invalid reasoning text length")]
     InvalidReasoningLength,
+    #[msg("Max reveal attempts must be between 1 and 10")]
+    InvalidRevealAttempts,
+    #[msg("Reveal attempts exhausted; commit is locked")]
+    RevealLocked,
+    #[msg("Reveal window must be between 1 second and MAX_REVEAL_WINDOW_SECONDS")]
+    InvalidRevealWindow,
+    #[msg("Reveal window has expired")]
+    RevealWindowExpired,
+    #[msg("Reveal window has not expired yet")]
+    RevealWindowOpen,
+    #[msg("Commit is already marked overdue")]
+    AlreadyOverdue,
+    #[msg("Only the registry auditor may adjust accuracy")]
+    UnauthorizedAuditor,
+    #[msg("Reasoning commit is still within its retention period")]
+    RetentionPeriodActive,
+    #[msg("Action plan must contain at least one action")]
+    EmptyActionPlan,
+    #[msg("Action plan exceeds maximum of 6 actions")]
+    ActionPlanTooLong,
+    #[msg("Action plan cannot repeat the same action consecutively")]
+    RepeatedAction,
+    #[msg("Cannot dispute your own reasoning")]
+    CannotDisputeOwn,
+    #[msg("Already disputed this reasoning")]
+    AlreadyDisputed,
+    #[msg("Reasoning has reached the maximum number of disputes")]
+    DisputeLimitReached,
+    #[msg("Dispute reason exceeds maximum length")]
+    DisputeReasonTooLong,
+    #[msg("Registered threat id does not match the committed threat id")]
+    ThreatIdMismatch,
+    #[msg("Commit can no longer be amended")]
+    CommitLocked,
+    #[msg("Disputer must be an active registered agent")]
+    DisputerInactive,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SALT: [u8; 32] = [7; 32];
+    const TEXT: &str = "drainer contract matches known rug pattern";
+    const DEADLINE: i64 = 1_000;
+
+    fn error_code(err: Error) -> u32 {
+        match err {
+            Error::AnchorError(e) => e.error_code_number,
+            Error::ProgramError(e) => panic!("unexpected program error: {e}"),
+        }
+    }
+
+    fn commit() -> ReasoningCommit {
+        ReasoningCommit {
+            agent_id: Pubkey::new_unique(),
+            authority: Pubkey::new_unique(),
+            delegate: None,
+            reasoning_hash: salted_hash(&SALT, TEXT),
+            threat_id: 1,
+            action_plan: vec![],
+            commit_timestamp: 0,
+            revealed: false,
+            reveal_timestamp: None,
+            reasoning_text: String::new(),
+            salt: [0; 32],
+            failed_reveal_attempts: 0,
+            max_reveal_attempts: 2,
+            reveal_failed: false,
+            reveal_deadline: DEADLINE,
+            reveal_overdue: false,
+            close_after: 0,
+            disputed: false,
+            dispute_count: 0,
+            disputers: vec![],
+            contested: false,
+            revision: 0,
+            reveal_promptness: 0,
+            bump: 0,
+        }
+    }
+
+    fn registry() -> AgentRegistry {
+        AgentRegistry {
+            agent_id: Pubkey::new_unique(),
+            total_commits: 1,
+            total_reveals: 0,
+            accuracy_score: 100,
+            promptness_sum: 0,
+            promptness_count: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn reveal_within_deadline_records_reveal() {
+        let (mut commit, mut registry) = (commit(), registry());
+
+        let valid = reveal_commit(&mut commit, &mut registry, TEXT.to_string(), SALT, DEADLINE)
+            .unwrap();
+
+        assert!(valid);
+        assert!(commit.revealed);
+        assert_eq!(commit.reveal_timestamp, Some(DEADLINE));
+        assert_eq!(commit.close_after, DEADLINE + REASONING_RETENTION_SECONDS);
+        assert_eq!(registry.total_reveals, 1);
+        assert_eq!(registry.promptness_count, 1);
+        // A successful reveal doesn't move accuracy on its own
+        assert_eq!(registry.accuracy_score, 100);
+    }
+
+    #[test]
+    fn reveal_after_deadline_is_rejected() {
+        let (mut commit, mut registry) = (commit(), registry());
+
+        let err = reveal_commit(&mut commit, &mut registry, TEXT.to_string(), SALT, DEADLINE + 1)
+            .unwrap_err();
+
+        assert_eq!(error_code(err), u32::from(ErrorCode::RevealWindowExpired));
+        assert!(!commit.revealed);
+    }
+
+    #[test]
+    fn exhausted_attempts_lock_and_penalize_once() {
+        let (mut commit, mut registry) = (commit(), registry());
+
+        let first =
+            reveal_commit(&mut commit, &mut registry, "wrong".to_string(), SALT, 0).unwrap();
+        assert!(!first);
+        assert!(!commit.reveal_failed);
+        assert_eq!(registry.accuracy_score, 100);
+
+        let second =
+            reveal_commit(&mut commit, &mut registry, "wrong".to_string(), SALT, 0).unwrap();
+        assert!(!second);
+        assert!(commit.reveal_failed);
+        assert_eq!(registry.accuracy_score, 100 - REVEAL_FAILURE_PENALTY);
+
+        // Even the correct text can't be revealed once locked
+        let err =
+            reveal_commit(&mut commit, &mut registry, TEXT.to_string(), SALT, 0).unwrap_err();
+        assert_eq!(error_code(err), u32::from(ErrorCode::RevealLocked));
+        assert_eq!(registry.accuracy_score, 100 - REVEAL_FAILURE_PENALTY);
+    }
+
+    #[test]
+    fn reveal_twice_is_rejected() {
+        let (mut commit, mut registry) = (commit(), registry());
+        reveal_commit(&mut commit, &mut registry, TEXT.to_string(), SALT, 0).unwrap();
+
+        let err =
+            reveal_commit(&mut commit, &mut registry, TEXT.to_string(), SALT, 0).unwrap_err();
+
+        assert_eq!(error_code(err), u32::from(ErrorCode::AlreadyRevealed));
+        assert_eq!(registry.total_reveals, 1);
+    }
+
+    #[test]
+    fn reveal_window_is_bounded() {
+        for window in [0, MAX_REVEAL_WINDOW_SECONDS + 1, i64::MAX] {
+            let (mut commit, mut registry) = (commit(), registry());
+            let agent_id = commit.agent_id;
+
+            let err = record_commit(
+                &mut commit,
+                &mut registry,
+                agent_id,
+                agent_id,
+                [1; 32],
+                1,
+                vec![ActionType::Monitor],
+                3,
+                window,
+                None,
+                0,
+            )
+            .unwrap_err();
+
+            assert_eq!(error_code(err), u32::from(ErrorCode::InvalidRevealWindow));
+        }
+    }
 }
@@ -1,7 +1,37 @@
+#![allow(clippy::too_many_arguments)] // Anchor instruction handlers take their args positionally
+
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::system_program;
+use agent_coordinator::program::AgentCoordinator;
+use agent_coordinator::{
+    urgency_from_severity, AgentRegistration, Capability, Coordination, CoordinationStatus,
+    ExecutionOutcome, SwarmConfig, SwarmRegistry, Urgency,
+};
 
 declare_id!("Fo9Nm41pvuUCT2sPPsuo1XyWCQCTKf6GNwASQ1ZMEfpv"); // Will be updated after first build
 
+/// Confirmations at which a confirmed threat's severity is bumped automatically
+pub const AUTO_SEVERITY_CONFIRMATIONS: usize = 5;
+
+/// Severity added by the automatic bump
+pub const AUTO_SEVERITY_BUMP: u8 = 10;
+
+/// Capacity of a threat's confirmed_by list
+pub const MAX_CONFIRMERS: usize = 10;
+
+/// Capacity of a threat's false_positive_voters list
+pub const MAX_FALSE_POSITIVE_VOTERS: usize = 10;
+
+/// Largest evidence blob accepted by verify_threat_evidence (fits in one transaction)
+pub const MAX_EVIDENCE_LEN: usize = 1000;
+
+/// Most watchlist entries checked in one batch_check_watchlist call
+pub const MAX_WATCHLIST_BATCH: usize = 20;
+
+/// Requested lifetime of an automatically initiated coordination
+pub const AUTO_COORDINATION_EXPIRY: i64 = 24 * 60 * 60;
+
 #[program]
 pub mod threat_intelligence {
     use super::*;
@@ -11,49 +41,125 @@ pub mod threat_intelligence {
         let counter = &mut ctx.accounts.threat_counter;
         counter.count = 0;
         counter.authority = ctx.accounts.authority.key();
+        counter.confirmation_reward = 1;
+        counter.required_confirmations = 3;
+        counter.aging_window_seconds = 7 * 24 * 60 * 60;
+        counter.aging_severity_decay = 10;
+        counter.total_confirmed = 0;
+        counter.total_false_positive = 0;
+        counter.severity_sum = 0;
+        counter.active_threats = 0;
+        counter.active_severity_sum = 0;
+        counter.severity_buckets = [0; 5];
+        counter.rate_limit_window_seconds = 60 * 60;
+        counter.rate_limit_max = 60;
         counter.bump = ctx.bumps.threat_counter;
         
         msg!("Threat counter initialized");
         Ok(())
     }
 
-    /// Register a new threat detected by an agent
+    /// Register a new threat detected by an agent, returning its threat id
     pub fn register_threat(
         ctx: Context<RegisterThreat>,
         threat_type: ThreatType,
         severity: u8,
         target_address: Option<Pubkey>,
+        target_kind: TargetKind,
         description: String,
         evidence_hash: [u8; 32],
-    ) -> Result<()> {
+        evidence_uri: String,
+        auto_coordinate: bool,
+    ) -> Result<u64> {
         require!(severity <= 100, ErrorCode::InvalidSeverity);
         require!(description.len() <= 500, ErrorCode::DescriptionTooLong);
+        require!(
+            !evidence_uri.is_empty() && evidence_uri.len() <= 200,
+            ErrorCode::InvalidEvidenceUri
+        );
 
         let counter = &mut ctx.accounts.threat_counter;
         let threat = &mut ctx.accounts.threat;
         let clock = Clock::get()?;
 
+        // Cap registrations per authority within each window
+        let rate_limit = &mut ctx.accounts.rate_limit;
+        if rate_limit.authority == Pubkey::default() {
+            rate_limit.authority = ctx.accounts.authority.key();
+            rate_limit.bump = ctx.bumps.rate_limit;
+        }
+        if clock.unix_timestamp
+            >= rate_limit.window_start.saturating_add(counter.rate_limit_window_seconds)
+        {
+            rate_limit.window_start = clock.unix_timestamp;
+            rate_limit.count_in_window = 0;
+        }
+        require!(
+            rate_limit.count_in_window < counter.rate_limit_max,
+            ErrorCode::RateLimited
+        );
+        rate_limit.count_in_window += 1;
+
         threat.threat_id = counter.count;
         threat.threat_type = threat_type;
         threat.severity = severity;
         threat.target_address = target_address;
+        threat.target_kind = target_kind;
         threat.description = description.clone();
         threat.evidence_hash = evidence_hash;
+        threat.evidence_uri = evidence_uri.clone();
         threat.detected_at = clock.unix_timestamp;
         threat.detected_by = ctx.accounts.authority.key();
         threat.status = ThreatStatus::Active;
         threat.confirmed_by = vec![];
         threat.false_positive_votes = 0;
+        threat.false_positive_voters = vec![];
+        threat.confirmer_reward = 0;
+        threat.reward_deltas = vec![];
+        threat.response_coordination_id = None;
+        threat.auto_coordinate = auto_coordinate;
+        threat.recovery_tx_hash = None;
+        threat.recovered_amount = 0;
+        threat.last_aged = 0;
+        threat.merged_into = None;
+        threat.severity_sum = severity as u16; // the detector's estimate is the first vote
+        threat.severity_votes = 1;
+        threat.bounty_amount = 0;
+        threat.bounty_claimed = false;
+        threat.counted_confirmed = false;
+        threat.counted_false_positive = false;
         threat.bump = ctx.bumps.threat;
 
         counter.count += 1;
+        counter.severity_sum += severity as u64;
+        counter.active_threats += 1;
+        counter.active_severity_sum += severity as u64;
+        counter.severity_buckets[severity_bucket(severity)] += 1;
+
+        // Record the threat under its type so clients can page through by type
+        let type_index = &mut ctx.accounts.threat_type_index;
+        if type_index.count == 0 {
+            type_index.threat_type = threat_type;
+            type_index.bump = ctx.bumps.threat_type_index;
+        }
+        let type_local_id = type_index.count;
+        type_index.count += 1;
+
+        let type_entry = &mut ctx.accounts.threat_of_type;
+        type_entry.threat_type = threat_type;
+        type_entry.type_local_id = type_local_id;
+        type_entry.threat_id = threat.threat_id;
+        type_entry.bump = ctx.bumps.threat_of_type;
 
         emit!(ThreatRegistered {
             threat_id: threat.threat_id,
             threat_type,
             severity,
             target_address,
+            target_kind,
             detected_by: ctx.accounts.authority.key(),
+            evidence_uri,
+            type_local_id,
             timestamp: clock.unix_timestamp,
         });
 
@@ -63,14 +169,28 @@ pub mod threat_intelligence {
             threat_type,
             severity
         );
-        Ok(())
+        Ok(threat.threat_id)
     }
 
     /// Confirm a threat (another agent validates it)
-    pub fn confirm_threat(ctx: Context<ConfirmThreat>) -> Result<()> {
+    /// On escalation, confirmers' AgentRegistrations are expected in remaining_accounts,
+    /// and an opted-in threat initiates a swarm coordination through the optional accounts.
+    /// That coordination is initiated by this confirmer, who pays its rent and receipt rent
+    /// plus SwarmParams.coordination_bond; the bond is refunded by reclaim_bond once the
+    /// coordination is decided and forfeited if it is cancelled or expires. Without the
+    /// optional accounts, or while the swarm is paused, the coordination is skipped
+    pub fn confirm_threat<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ConfirmThreat<'info>>,
+        confirmed_severity: u8,
+    ) -> Result<()> {
+        require!(confirmed_severity <= 100, ErrorCode::InvalidSeverity);
+
         let threat = &mut ctx.accounts.threat;
         let confirmer = ctx.accounts.authority.key();
 
+        // Only active agents with a capability suited to this threat type may confirm
+        require_confirmer_capability(&ctx.accounts.agent_registration, threat.threat_type)?;
+
         // Can't confirm your own threat
         require!(threat.detected_by != confirmer, ErrorCode::CannotConfirmOwn);
         
@@ -80,17 +200,20 @@ pub mod threat_intelligence {
             ErrorCode::AlreadyConfirmed
         );
 
+        let previous_confirmations = threat.confirmed_by.len();
         threat.confirmed_by.push(confirmer);
+        threat.severity_sum += confirmed_severity as u16;
+        threat.severity_votes += 1;
 
-        // Auto-escalate if 3+ confirmations
-        if threat.confirmed_by.len() >= 3 && threat.status == ThreatStatus::Active {
-            threat.status = ThreatStatus::Confirmed;
-            emit!(ThreatEscalated {
-                threat_id: threat.threat_id,
-                new_status: ThreatStatus::Confirmed,
-                confirmations: threat.confirmed_by.len() as u8,
-                timestamp: Clock::get()?.unix_timestamp,
-            });
+        let escalated = apply_confirmation_thresholds(
+            threat,
+            previous_confirmations,
+            &mut ctx.accounts.threat_counter,
+            &ctx.accounts.coordinator_program,
+            ctx.remaining_accounts,
+        )?;
+        if escalated {
+            count_status_entry(&mut ctx.accounts.threat_counter, threat, ThreatStatus::Confirmed);
         }
 
         emit!(ThreatConfirmed {
@@ -100,38 +223,106 @@ pub mod threat_intelligence {
             timestamp: Clock::get()?.unix_timestamp,
         });
 
+        if escalated && threat.auto_coordinate {
+            let (threat_id, severity) = (threat.threat_id, threat.severity);
+            let accounts = &ctx.accounts;
+            let coordination_id = trigger_auto_coordination(
+                threat_id,
+                severity,
+                AutoCoordinationAccounts {
+                    coordinator_program: &accounts.coordinator_program,
+                    threat: &accounts.threat,
+                    authority: &accounts.authority,
+                    coordination: &accounts.coordination,
+                    initiation_receipt: &accounts.initiation_receipt,
+                    swarm_registry: &accounts.swarm_registry,
+                    swarm_config: &accounts.swarm_config,
+                    system_program: &accounts.system_program,
+                },
+            )?;
+            if coordination_id.is_some() {
+                ctx.accounts.threat.response_coordination_id = coordination_id;
+            }
+        }
+
         Ok(())
     }
 
     /// Mark threat as false positive
-    pub fn mark_false_positive(ctx: Context<MarkFalsePositive>) -> Result<()> {
+    /// If confirmers were rewarded, their AgentRegistrations are expected in remaining_accounts
+    pub fn mark_false_positive<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MarkFalsePositive<'info>>,
+    ) -> Result<()> {
         let threat = &mut ctx.accounts.threat;
-        
-        threat.false_positive_votes += 1;
+        let voter = ctx.accounts.authority.key();
+        record_false_positive_vote(threat, voter)?;
+
+        emit!(FalsePositiveVoted {
+            threat_id: threat.threat_id,
+            voter,
+            total_votes: threat.false_positive_votes,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        // If 3+ false positive votes, mark as false positive
-        if threat.false_positive_votes >= 3 {
-            threat.status = ThreatStatus::FalsePositive;
+        // Enough votes for this threat's severity and standing marks it a false positive
+        let old_status = threat.status;
+        if threat.false_positive_votes >= false_positive_threshold(threat.severity, old_status)
+            && can_transition(old_status, ThreatStatus::FalsePositive)
+        {
+            set_threat_status(
+                &mut ctx.accounts.threat_counter,
+                threat,
+                ThreatStatus::FalsePositive,
+            );
+            count_status_entry(
+                &mut ctx.accounts.threat_counter,
+                threat,
+                ThreatStatus::FalsePositive,
+            );
             emit!(ThreatStatusChanged {
                 threat_id: threat.threat_id,
-                old_status: ThreatStatus::Active,
+                old_status,
                 new_status: ThreatStatus::FalsePositive,
                 timestamp: Clock::get()?.unix_timestamp,
             });
+
+            clawback_confirmer_reward(
+                threat,
+                &ctx.accounts.threat_counter,
+                &ctx.accounts.coordinator_program,
+                ctx.remaining_accounts,
+            )?;
         }
 
         Ok(())
     }
 
-    /// Update threat status
-    pub fn update_threat_status(
-        ctx: Context<UpdateThreatStatus>,
+    /// Update threat status (counter authority only)
+    /// Moving to FalsePositive claws back the rewarded confirmers, whose
+    /// AgentRegistrations are then expected in remaining_accounts
+    pub fn update_threat_status<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdateThreatStatus<'info>>,
         new_status: ThreatStatus,
     ) -> Result<()> {
         let threat = &mut ctx.accounts.threat;
-        let old_status = threat.status.clone();
+        let old_status = threat.status;
+
+        require!(
+            can_transition(old_status, new_status),
+            ErrorCode::IllegalStatusTransition
+        );
 
-        threat.status = new_status.clone();
+        set_threat_status(&mut ctx.accounts.threat_counter, threat, new_status);
+        count_status_entry(&mut ctx.accounts.threat_counter, threat, new_status);
+
+        if new_status == ThreatStatus::FalsePositive {
+            clawback_confirmer_reward(
+                threat,
+                &ctx.accounts.threat_counter,
+                &ctx.accounts.coordinator_program,
+                ctx.remaining_accounts,
+            )?;
+        }
 
         emit!(ThreatStatusChanged {
             threat_id: threat.threat_id,
@@ -152,22 +343,36 @@ pub mod threat_intelligence {
     pub fn add_to_watchlist(
         ctx: Context<AddToWatchlist>,
         address: Pubkey,
+        category: WatchlistCategory,
         reason: String,
         linked_threat_id: Option<u64>,
+        risk_score: u8,
+        expires_at: Option<i64>,
     ) -> Result<()> {
+        require!(risk_score <= 100, ErrorCode::InvalidRiskScore);
+
         let watchlist_entry = &mut ctx.accounts.watchlist_entry;
         let clock = Clock::get()?;
 
+        if let Some(expiry) = expires_at {
+            require!(expiry > clock.unix_timestamp, ErrorCode::InvalidExpiry);
+        }
+
         watchlist_entry.address = address;
+        watchlist_entry.category = category;
         watchlist_entry.reason = reason;
         watchlist_entry.linked_threat_id = linked_threat_id;
+        watchlist_entry.risk_score = risk_score;
         watchlist_entry.added_at = clock.unix_timestamp;
         watchlist_entry.added_by = ctx.accounts.authority.key();
         watchlist_entry.active = true;
+        watchlist_entry.deactivated_at = 0;
+        watchlist_entry.expires_at = expires_at;
         watchlist_entry.bump = ctx.bumps.watchlist_entry;
 
         emit!(AddressWatchlisted {
             address,
+            category,
             linked_threat_id,
             added_by: ctx.accounts.authority.key(),
             timestamp: clock.unix_timestamp,
@@ -177,211 +382,2059 @@ pub mod threat_intelligence {
         Ok(())
     }
 
-    /// Check if an address is on the watchlist
-    pub fn check_watchlist(ctx: Context<CheckWatchlist>) -> Result<bool> {
-        Ok(ctx.accounts.watchlist_entry.active)
+    /// Check if an address is on the watchlist, and how risky it is
+    /// An entry past its expiry reads as inactive
+    pub fn check_watchlist(ctx: Context<CheckWatchlist>) -> Result<WatchlistStatus> {
+        let entry = &ctx.accounts.watchlist_entry;
+        let now = Clock::get()?.unix_timestamp;
+        Ok(WatchlistStatus {
+            active: is_watched(entry, now),
+            risk_score: entry.risk_score,
+            category: entry.category,
+            linked_threat_id: entry.linked_threat_id,
+        })
     }
-}
 
-// ============== ACCOUNTS ==============
+    /// Set the reputation bonus granted to confirmers when a threat escalates
+    pub fn set_confirmation_reward(
+        ctx: Context<SetConfirmationReward>,
+        amount: u8,
+    ) -> Result<()> {
+        require!(amount <= 100, ErrorCode::InvalidReward);
 
-#[derive(Accounts)]
-pub struct InitializeThreatCounter<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + ThreatCounter::INIT_SPACE,
-        seeds = [b"threat_counter"],
-        bump
-    )]
-    pub threat_counter: Account<'info, ThreatCounter>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+        ctx.accounts.threat_counter.confirmation_reward = amount;
 
-#[derive(Accounts)]
-pub struct RegisterThreat<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + Threat::INIT_SPACE,
-        seeds = [b"threat", threat_counter.count.to_le_bytes().as_ref()],
-        bump
-    )]
-    pub threat: Account<'info, Threat>,
-    
-    #[account(mut, seeds = [b"threat_counter"], bump = threat_counter.bump)]
-    pub threat_counter: Account<'info, ThreatCounter>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+        msg!("Confirmation reward set to {}", amount);
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct ConfirmThreat<'info> {
-    #[account(mut)]
-    pub threat: Account<'info, Threat>,
-    
-    pub authority: Signer<'info>,
-}
+    /// Clear an address from the watchlist (e.g. once exonerated)
+    /// Only the original reporter or the threat counter authority may delist
+    pub fn deactivate_watchlist_entry(ctx: Context<DeactivateWatchlist>) -> Result<()> {
+        let watchlist_entry = &mut ctx.accounts.watchlist_entry;
+        let signer = ctx.accounts.authority.key();
+        let clock = Clock::get()?;
 
-#[derive(Accounts)]
-pub struct MarkFalsePositive<'info> {
-    #[account(mut)]
-    pub threat: Account<'info, Threat>,
-    
-    pub authority: Signer<'info>,
-}
+        require!(
+            signer == watchlist_entry.added_by || signer == ctx.accounts.threat_counter.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(watchlist_entry.active, ErrorCode::AlreadyInactive);
 
-#[derive(Accounts)]
-pub struct UpdateThreatStatus<'info> {
-    #[account(mut)]
-    pub threat: Account<'info, Threat>,
-    
-    pub authority: Signer<'info>,
-}
+        watchlist_entry.active = false;
+        watchlist_entry.deactivated_at = clock.unix_timestamp;
 
-#[derive(Accounts)]
-#[instruction(address: Pubkey)]
-pub struct AddToWatchlist<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + WatchlistEntry::INIT_SPACE,
-        seeds = [b"watchlist", address.as_ref()],
-        bump
-    )]
-    pub watchlist_entry: Account<'info, WatchlistEntry>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+        emit!(AddressDelisted {
+            address: watchlist_entry.address,
+            delisted_by: signer,
+            timestamp: clock.unix_timestamp,
+        });
 
-#[derive(Accounts)]
-pub struct CheckWatchlist<'info> {
-    pub watchlist_entry: Account<'info, WatchlistEntry>,
-}
+        msg!("Removed {} from watchlist", watchlist_entry.address);
+        Ok(())
+    }
 
-// ============== STATE ==============
+    /// Raise a confirmed threat's severity (capped at 100)
+    pub fn escalate_severity(ctx: Context<EscalateSeverity>, delta: u8) -> Result<()> {
+        let threat = &mut ctx.accounts.threat;
 
-#[account]
-#[derive(InitSpace)]
-pub struct ThreatCounter {
-    pub count: u64,
-    pub authority: Pubkey,
-    pub bump: u8,
-}
+        require!(
+            threat.status == ThreatStatus::Confirmed,
+            ErrorCode::NotConfirmed
+        );
 
-#[account]
-#[derive(InitSpace)]
-pub struct Threat {
-    pub threat_id: u64,
-    pub threat_type: ThreatType,
-    pub severity: u8, // 0-100
-    pub target_address: Option<Pubkey>,
-    #[max_len(500)]
-    pub description: String,
-    pub evidence_hash: [u8; 32],
-    pub detected_at: i64,
-    pub detected_by: Pubkey,
-    pub status: ThreatStatus,
-    #[max_len(10)]
-    pub confirmed_by: Vec<Pubkey>,
-    pub false_positive_votes: u8,
-    pub bump: u8,
-}
+        raise_severity(&mut ctx.accounts.threat_counter, threat, delta)
+    }
 
-#[account]
-#[derive(InitSpace)]
-pub struct WatchlistEntry {
-    pub address: Pubkey,
-    #[max_len(200)]
-    pub reason: String,
-    pub linked_threat_id: Option<u64>,
-    pub added_at: i64,
-    pub added_by: Pubkey,
-    pub active: bool,
-    pub bump: u8,
-}
+    /// Link a threat to the swarm coordination that responded to it
+    /// Only the coordination's initiator may link, and an existing link is never overwritten
+    pub fn link_response(ctx: Context<LinkResponse>, coordination_id: u64) -> Result<()> {
+        let threat = &mut ctx.accounts.threat;
 
-// ============== TYPES ==============
+        require!(
+            threat.response_coordination_id.is_none(),
+            ErrorCode::ResponseAlreadyLinked
+        );
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
-pub enum ThreatType {
-    RugPull,
-    Honeypot,
-    PhishingContract,
-    SuspiciousTransfer,
-    PriceManipulation,
-    UnauthorizedMint,
-    FlashLoanAttack,
-    SandwichAttack,
-    DrainAttack,
-    Unknown,
-}
+        threat.response_coordination_id = Some(coordination_id);
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
-pub enum ThreatStatus {
-    Active,
-    Confirmed,
-    Neutralized,
-    FalsePositive,
-    UnderInvestigation,
-    Escalated,
-}
+        emit!(ThreatResponseLinked {
+            threat_id: threat.threat_id,
+            coordination_id,
+            linked_by: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-// ============== EVENTS ==============
+        msg!(
+            "Linked threat #{} to coordination #{}",
+            threat.threat_id,
+            coordination_id
+        );
+        Ok(())
+    }
 
-#[event]
-pub struct ThreatRegistered {
-    pub threat_id: u64,
-    pub threat_type: ThreatType,
-    pub severity: u8,
-    pub target_address: Option<Pubkey>,
-    pub detected_by: Pubkey,
-    pub timestamp: i64,
-}
+    /// Confirm a threat on behalf of several agents in one transaction
+    /// remaining_accounts: each confirmer as a signer, then each confirmer's AgentRegistration
+    /// (both in `confirmers` order), then on escalation the reward accounts as in confirm_threat
+    /// `confirmed_severities` holds each confirmer's severity vote, in `confirmers` order.
+    /// An opted-in threat initiates a swarm coordination through the optional accounts,
+    /// with `authority` paying rent and bond as described on confirm_threat
+    pub fn batch_confirm_threat<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchConfirmThreat<'info>>,
+        confirmers: Vec<Pubkey>,
+        confirmed_severities: Vec<u8>,
+    ) -> Result<()> {
+        let threat = &mut ctx.accounts.threat;
+        let remaining = ctx.remaining_accounts;
+        let batch_len = confirmers.len();
 
-#[event]
-pub struct ThreatConfirmed {
-    pub threat_id: u64,
-    pub confirmed_by: Pubkey,
-    pub total_confirmations: u8,
-    pub timestamp: i64,
-}
+        require!(
+            confirmed_severities.len() == batch_len,
+            ErrorCode::SeverityVoteMismatch
+        );
+        require!(
+            confirmed_severities.iter().all(|severity| *severity <= 100),
+            ErrorCode::InvalidSeverity
+        );
+        require!(
+            remaining.len() >= batch_len * 2,
+            ErrorCode::MissingConfirmerAccounts
+        );
 
-#[event]
-pub struct ThreatEscalated {
-    pub threat_id: u64,
-    pub new_status: ThreatStatus,
-    pub confirmations: u8,
-    pub timestamp: i64,
-}
+        let mut added: Vec<Pubkey> = vec![];
+        let mut added_severities: Vec<u8> = vec![];
+        for (i, confirmer) in confirmers.iter().enumerate() {
+            // Dedupe within the batch
+            if added.contains(confirmer) {
+                continue;
+            }
 
-#[event]
-pub struct ThreatStatusChanged {
-    pub threat_id: u64,
-    pub old_status: ThreatStatus,
-    pub new_status: ThreatStatus,
-    pub timestamp: i64,
-}
+            let signer_info = &remaining[i];
+            require_keys_eq!(signer_info.key(), *confirmer, ErrorCode::MissingConfirmerAccounts);
+            require!(signer_info.is_signer, ErrorCode::ConfirmerNotSigner);
 
-#[event]
-pub struct AddressWatchlisted {
-    pub address: Pubkey,
-    pub linked_threat_id: Option<u64>,
-    pub added_by: Pubkey,
-    pub timestamp: i64,
-}
+            let registration = Account::<AgentRegistration>::try_from(&remaining[batch_len + i])?;
+            require_keys_eq!(
+                registration.agent_id,
+                *confirmer,
+                ErrorCode::MissingConfirmerAccounts
+            );
+            require_confirmer_capability(&registration, threat.threat_type)?;
 
-// ============== ERRORS ==============
+            require!(threat.detected_by != *confirmer, ErrorCode::CannotConfirmOwn);
+            require!(
+                !threat.confirmed_by.contains(confirmer),
+                ErrorCode::AlreadyConfirmed
+            );
+
+            added.push(*confirmer);
+            added_severities.push(confirmed_severities[i]);
+        }
+
+        // Never grow past the confirmed_by capacity
+        require!(
+            threat.confirmed_by.len() + added.len() <= MAX_CONFIRMERS,
+            ErrorCode::TooManyConfirmers
+        );
+
+        let previous_confirmations = threat.confirmed_by.len();
+        threat.confirmed_by.extend(added.iter().copied());
+        for severity in added_severities {
+            threat.severity_sum += severity as u16;
+            threat.severity_votes += 1;
+        }
+
+        let escalated = apply_confirmation_thresholds(
+            threat,
+            previous_confirmations,
+            &mut ctx.accounts.threat_counter,
+            &ctx.accounts.coordinator_program,
+            &remaining[batch_len * 2..],
+        )?;
+        if escalated {
+            count_status_entry(&mut ctx.accounts.threat_counter, threat, ThreatStatus::Confirmed);
+        }
+
+        emit!(BatchThreatConfirmed {
+            threat_id: threat.threat_id,
+            confirmers: added,
+            total_confirmations: threat.confirmed_by.len() as u8,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        if escalated && threat.auto_coordinate {
+            let (threat_id, severity) = (threat.threat_id, threat.severity);
+            let accounts = &ctx.accounts;
+            let coordination_id = trigger_auto_coordination(
+                threat_id,
+                severity,
+                AutoCoordinationAccounts {
+                    coordinator_program: &accounts.coordinator_program,
+                    threat: &accounts.threat,
+                    authority: &accounts.authority,
+                    coordination: &accounts.coordination,
+                    initiation_receipt: &accounts.initiation_receipt,
+                    swarm_registry: &accounts.swarm_registry,
+                    swarm_config: &accounts.swarm_config,
+                    system_program: &accounts.system_program,
+                },
+            )?;
+            if coordination_id.is_some() {
+                ctx.accounts.threat.response_coordination_id = coordination_id;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adjust a watchlisted address's risk score as intelligence evolves
+    /// Only the original reporter or the threat counter authority may adjust
+    pub fn update_watchlist_risk(ctx: Context<UpdateWatchlistRisk>, new_score: u8) -> Result<()> {
+        require!(new_score <= 100, ErrorCode::InvalidRiskScore);
+
+        let watchlist_entry = &mut ctx.accounts.watchlist_entry;
+        let signer = ctx.accounts.authority.key();
+
+        require!(
+            signer == watchlist_entry.added_by || signer == ctx.accounts.threat_counter.authority,
+            ErrorCode::Unauthorized
+        );
+
+        let old_score = watchlist_entry.risk_score;
+        watchlist_entry.risk_score = new_score;
+
+        emit!(WatchlistRiskUpdated {
+            address: watchlist_entry.address,
+            old_score,
+            new_score,
+            updated_by: signer,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!(
+            "Risk score for {} updated to {}",
+            watchlist_entry.address,
+            new_score
+        );
+        Ok(())
+    }
+
+    /// Push out a time-bounded watchlist entry's expiry
+    /// Only the original reporter or the threat counter authority may extend
+    pub fn extend_watchlist(ctx: Context<UpdateWatchlistRisk>, new_expiry: i64) -> Result<()> {
+        let watchlist_entry = &mut ctx.accounts.watchlist_entry;
+        let signer = ctx.accounts.authority.key();
+        let clock = Clock::get()?;
+
+        require!(
+            signer == watchlist_entry.added_by || signer == ctx.accounts.threat_counter.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(
+            new_expiry > clock.unix_timestamp
+                && !matches!(watchlist_entry.expires_at, Some(expiry) if new_expiry <= expiry),
+            ErrorCode::InvalidExpiry
+        );
+
+        let old_expiry = watchlist_entry.expires_at;
+        watchlist_entry.expires_at = Some(new_expiry);
+
+        emit!(WatchlistExtended {
+            address: watchlist_entry.address,
+            old_expiry,
+            new_expiry,
+            extended_by: signer,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Watchlist entry {} extended to {}", watchlist_entry.address, new_expiry);
+        Ok(())
+    }
+
+    /// Record a threat's resolution by the fund-recovery (Healer) workflow
+    pub fn neutralize_threat(
+        ctx: Context<NeutralizeThreat>,
+        recovery_tx_hash: [u8; 32],
+        recovered_amount: u64,
+    ) -> Result<()> {
+        let agent = &ctx.accounts.agent_registration;
+        require!(
+            agent.active && agent.capabilities.contains(&Capability::FundRecovery),
+            ErrorCode::NotAuthorizedRecoverer
+        );
+
+        let threat = &mut ctx.accounts.threat;
+        require!(
+            threat.status == ThreatStatus::Confirmed || threat.status == ThreatStatus::Escalated,
+            ErrorCode::CannotNeutralize
+        );
+
+        let old_status = threat.status;
+        set_threat_status(&mut ctx.accounts.threat_counter, threat, ThreatStatus::Neutralized);
+        threat.recovery_tx_hash = Some(recovery_tx_hash);
+        threat.recovered_amount = recovered_amount;
+
+        emit!(ThreatNeutralized {
+            threat_id: threat.threat_id,
+            old_status,
+            recovery_tx_hash,
+            recovered_amount,
+            neutralized_by: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!(
+            "Threat #{} neutralized, {} recovered",
+            threat.threat_id,
+            recovered_amount
+        );
+        Ok(())
+    }
+
+    /// Check several watchlist entries (passed in remaining_accounts) in one call
+    /// Returns one flag per account in order; accounts that aren't watchlist
+    /// entries read as not watched
+    pub fn batch_check_watchlist<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchCheckWatchlist<'info>>,
+    ) -> Result<Vec<bool>> {
+        require!(
+            ctx.remaining_accounts.len() <= MAX_WATCHLIST_BATCH,
+            ErrorCode::TooManyAddresses
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        Ok(ctx
+            .remaining_accounts
+            .iter()
+            .map(|info| {
+                Account::<WatchlistEntry>::try_from(info).is_ok_and(|entry| is_watched(&entry, now))
+            })
+            .collect())
+    }
+
+    /// Check a submitted evidence blob against the hash committed at registration
+    pub fn verify_threat_evidence(
+        ctx: Context<VerifyThreatEvidence>,
+        evidence: Vec<u8>,
+    ) -> Result<bool> {
+        require!(evidence.len() <= MAX_EVIDENCE_LEN, ErrorCode::EvidenceTooLarge);
+
+        let threat = &ctx.accounts.threat;
+        let is_valid = hash(&evidence).to_bytes() == threat.evidence_hash;
+
+        emit!(ThreatEvidenceVerified {
+            threat_id: threat.threat_id,
+            verified_by: ctx.accounts.authority.key(),
+            is_valid,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(is_valid)
+    }
+
+    /// Reopen a threat buried as a false positive (threat counter authority only)
+    pub fn reopen_threat(ctx: Context<ReopenThreat>) -> Result<()> {
+        let threat = &mut ctx.accounts.threat;
+
+        require!(
+            threat.status == ThreatStatus::FalsePositive,
+            ErrorCode::NotFalsePositive
+        );
+
+        threat.status = ThreatStatus::UnderInvestigation;
+        threat.false_positive_votes = 0;
+        threat.false_positive_voters = vec![];
+
+        emit!(ThreatReopened {
+            threat_id: threat.threat_id,
+            reopened_by: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Threat #{} reopened for investigation", threat.threat_id);
+        Ok(())
+    }
+
+    /// Set the base confirmation count for auto-escalation
+    pub fn update_required_confirmations(
+        ctx: Context<SetConfirmationReward>,
+        n: u8,
+    ) -> Result<()> {
+        // Leave room for the low-severity bump within confirmed_by's capacity
+        require!(
+            n >= 1 && (n as usize) + 2 <= MAX_CONFIRMERS,
+            ErrorCode::InvalidConfirmationCount
+        );
+
+        ctx.accounts.threat_counter.required_confirmations = n;
+
+        msg!("Required confirmations set to {}", n);
+        Ok(())
+    }
+
+    /// Set how long a threat may stay Active unconfirmed, and how much severity it loses when aged
+    pub fn set_aging_params(
+        ctx: Context<SetConfirmationReward>,
+        aging_window_seconds: i64,
+        aging_severity_decay: u8,
+    ) -> Result<()> {
+        require!(aging_window_seconds > 0, ErrorCode::InvalidAgingWindow);
+        require!(aging_severity_decay <= 100, ErrorCode::InvalidSeverity);
+
+        let counter = &mut ctx.accounts.threat_counter;
+        counter.aging_window_seconds = aging_window_seconds;
+        counter.aging_severity_decay = aging_severity_decay;
+
+        msg!(
+            "Aging window set to {}s with severity decay {}",
+            aging_window_seconds,
+            aging_severity_decay
+        );
+        Ok(())
+    }
+
+    /// Downgrade an Active threat left unconfirmed past the aging window (callable by anyone)
+    pub fn age_threat(ctx: Context<AgeThreat>) -> Result<()> {
+        let threat = &mut ctx.accounts.threat;
+        let counter = &mut ctx.accounts.threat_counter;
+        let clock = Clock::get()?;
+
+        require!(threat.status == ThreatStatus::Active, ErrorCode::NotAgeable);
+
+        // Measure from the later of detection and the last aging so one window decays once
+        let since = threat.detected_at.max(threat.last_aged);
+        require!(
+            clock.unix_timestamp - since > counter.aging_window_seconds,
+            ErrorCode::NotAgeable
+        );
+
+        let old_status = threat.status;
+        // An aged threat leaves the live aggregates before its severity decays
+        set_threat_status(counter, threat, ThreatStatus::UnderInvestigation);
+        let decayed = threat.severity.saturating_sub(counter.aging_severity_decay);
+        set_threat_severity(counter, threat, decayed);
+        threat.last_aged = clock.unix_timestamp;
+
+        emit!(ThreatStatusChanged {
+            threat_id: threat.threat_id,
+            old_status,
+            new_status: ThreatStatus::UnderInvestigation,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Threat #{} aged to UnderInvestigation with severity {}",
+            threat.threat_id,
+            threat.severity
+        );
+        Ok(())
+    }
+
+    /// Network-wide threat aggregates from the counter, without scanning threats
+    pub fn get_threat_stats(ctx: Context<GetThreatStats>) -> Result<ThreatStats> {
+        let counter = &ctx.accounts.threat_counter;
+        Ok(ThreatStats {
+            total_threats: counter.count,
+            total_confirmed: counter.total_confirmed,
+            total_false_positive: counter.total_false_positive,
+            severity_sum: counter.severity_sum,
+            mean_severity: counter.severity_sum.checked_div(counter.count).unwrap_or(0) as u8,
+        })
+    }
+
+    /// Registered threats by severity: [0-20, 21-40, 41-60, 61-80, 81-100]
+    pub fn get_severity_histogram(ctx: Context<GetThreatStats>) -> Result<[u64; 5]> {
+        Ok(ctx.accounts.threat_counter.severity_buckets)
+    }
+
+    /// Fold a duplicate threat into `primary_threat_id` (counter authority only)
+    /// The secondary's confirmers carry over, deduplicated and up to MAX_CONFIRMERS;
+    /// the primary re-checks its escalation threshold on its next confirmation.
+    /// Any bounty on the secondary goes back to its funders via refund_threat_bounty
+    pub fn merge_threats(ctx: Context<MergeThreats>, primary_threat_id: u64) -> Result<()> {
+        let primary = &mut ctx.accounts.primary_threat;
+        let secondary = &mut ctx.accounts.secondary_threat;
+
+        require!(
+            secondary.threat_id != primary_threat_id,
+            ErrorCode::CannotMergeIntoSelf
+        );
+        require!(
+            can_transition(secondary.status, ThreatStatus::Merged),
+            ErrorCode::IllegalStatusTransition
+        );
+        require!(can_absorb_merge(primary.status), ErrorCode::InvalidMergeTarget);
+
+        let transferred = merge_confirmers(primary, &secondary.confirmed_by);
+
+        let old_status = secondary.status;
+        set_threat_status(&mut ctx.accounts.threat_counter, secondary, ThreatStatus::Merged);
+        secondary.merged_into = Some(primary_threat_id);
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        emit!(ThreatStatusChanged {
+            threat_id: secondary.threat_id,
+            old_status,
+            new_status: ThreatStatus::Merged,
+            timestamp,
+        });
+        emit!(ThreatsMerged {
+            primary_threat_id,
+            secondary_threat_id: secondary.threat_id,
+            transferred_confirmers: transferred,
+            total_confirmations: primary.confirmed_by.len() as u8,
+            timestamp,
+        });
+
+        msg!(
+            "Merged threat #{} into #{}",
+            secondary.threat_id,
+            primary_threat_id
+        );
+        Ok(())
+    }
+
+    /// Set the per-authority threat registration cap and its window (counter authority only)
+    pub fn set_rate_limit(
+        ctx: Context<SetConfirmationReward>,
+        window_seconds: i64,
+        max_registrations: u16,
+    ) -> Result<()> {
+        require!(
+            window_seconds > 0 && max_registrations > 0,
+            ErrorCode::InvalidRateLimit
+        );
+
+        let counter = &mut ctx.accounts.threat_counter;
+        counter.rate_limit_window_seconds = window_seconds;
+        counter.rate_limit_max = max_registrations;
+
+        msg!(
+            "Rate limit set to {} registrations per {}s",
+            max_registrations,
+            window_seconds
+        );
+        Ok(())
+    }
+
+    /// Average of the detector's and confirmers' severity estimates
+    pub fn get_consensus_severity(ctx: Context<GetConsensusSeverity>) -> Result<u8> {
+        Ok(consensus_severity(&ctx.accounts.threat))
+    }
+
+    /// Attach a lamport bounty to a threat, held in the threat PDA until claimed.
+    /// Each funder's contribution is recorded so it can be refunded if the
+    /// threat is dismissed or merged
+    pub fn fund_threat_bounty(ctx: Context<FundThreatBounty>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::NoBounty);
+        require!(!ctx.accounts.threat.bounty_claimed, ErrorCode::BountyAlreadyClaimed);
+        require!(
+            !matches!(
+                ctx.accounts.threat.status,
+                ThreatStatus::FalsePositive | ThreatStatus::Merged
+            ),
+            ErrorCode::ThreatClosed
+        );
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.funder.to_account_info(),
+                    to: ctx.accounts.threat.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let contribution = &mut ctx.accounts.bounty_contribution;
+        if contribution.funder == Pubkey::default() {
+            contribution.threat_id = ctx.accounts.threat.threat_id;
+            contribution.funder = ctx.accounts.funder.key();
+            contribution.bump = ctx.bumps.bounty_contribution;
+        }
+
+        let threat = &mut ctx.accounts.threat;
+        record_bounty_contribution(threat, contribution, amount)?;
+
+        emit!(BountyFunded {
+            threat_id: threat.threat_id,
+            funder: ctx.accounts.funder.key(),
+            amount,
+            total_bounty: threat.bounty_amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Pay out a confirmed threat's bounty in equal shares to the detector and confirmers
+    /// remaining_accounts: `detected_by`, then each of `confirmed_by` in order (writable);
+    /// any rounding remainder goes to the detector
+    pub fn claim_threat_bounty<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimThreatBounty<'info>>,
+    ) -> Result<()> {
+        let threat = &mut ctx.accounts.threat;
+        let remaining = ctx.remaining_accounts;
+
+        require!(
+            matches!(
+                threat.status,
+                ThreatStatus::Confirmed | ThreatStatus::Escalated | ThreatStatus::Neutralized
+            ),
+            ErrorCode::NotConfirmed
+        );
+        require!(!threat.bounty_claimed, ErrorCode::BountyAlreadyClaimed);
+        require!(threat.bounty_amount > 0, ErrorCode::NoBounty);
+
+        let threat_info = threat.to_account_info();
+        let total = threat.bounty_amount;
+        let (recipients, share) = pay_bounty(threat, &threat_info, remaining)?;
+
+        emit!(BountyClaimed {
+            threat_id: threat.threat_id,
+            recipients,
+            share,
+            total,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Return a funder's contribution from a threat that was dismissed or merged
+    /// and so can never pay out. Closes the contribution record
+    pub fn refund_threat_bounty(ctx: Context<RefundThreatBounty>) -> Result<()> {
+        let threat_info = ctx.accounts.threat.to_account_info();
+        let funder_info = ctx.accounts.funder.to_account_info();
+        let threat = &mut ctx.accounts.threat;
+
+        let amount = refund_contribution(
+            threat,
+            &mut ctx.accounts.bounty_contribution,
+            &threat_info,
+            &funder_info,
+        )?;
+
+        emit!(BountyRefunded {
+            threat_id: threat.threat_id,
+            funder: ctx.accounts.funder.key(),
+            amount,
+            remaining_bounty: threat.bounty_amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Neutralize a threat once its response coordination executed with full success
+    /// Invoked by agent-coordinator, which signs with its swarm registry PDA; a threat
+    /// not yet in a neutralizable status is left unchanged
+    pub fn neutralize_from_coordination(ctx: Context<NeutralizeFromCoordination>) -> Result<()> {
+        let coordination = &ctx.accounts.coordination;
+        require!(
+            coordination.status == CoordinationStatus::Executed
+                && coordination.outcome == Some(ExecutionOutcome::FullSuccess),
+            ErrorCode::CoordinationNotSuccessful
+        );
+
+        let threat = &mut ctx.accounts.threat;
+        let old_status = threat.status;
+        if !can_transition(old_status, ThreatStatus::Neutralized) {
+            msg!(
+                "Threat #{} is {:?}; leaving it for manual resolution",
+                threat.threat_id,
+                old_status
+            );
+            return Ok(());
+        }
+
+        set_threat_status(&mut ctx.accounts.threat_counter, threat, ThreatStatus::Neutralized);
+        if threat.response_coordination_id.is_none() {
+            threat.response_coordination_id = Some(coordination.coordination_id);
+        }
+
+        emit!(ThreatNeutralizedByCoordination {
+            threat_id: threat.threat_id,
+            coordination_id: coordination.coordination_id,
+            old_status,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!(
+            "Threat #{} neutralized by coordination #{}",
+            threat.threat_id,
+            coordination.coordination_id
+        );
+        Ok(())
+    }
+}
+
+// ============== ACCOUNTS ==============
+
+#[derive(Accounts)]
+pub struct InitializeThreatCounter<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ThreatCounter::INIT_SPACE,
+        seeds = [b"threat_counter"],
+        bump
+    )]
+    pub threat_counter: Account<'info, ThreatCounter>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(threat_type: ThreatType)]
+pub struct RegisterThreat<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Threat::INIT_SPACE,
+        seeds = [b"threat", threat_counter.count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub threat: Account<'info, Threat>,
+    
+    #[account(mut, seeds = [b"threat_counter"], bump = threat_counter.bump)]
+    pub threat_counter: Account<'info, ThreatCounter>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ThreatTypeIndex::INIT_SPACE,
+        seeds = [b"threat_type_index".as_ref(), &[threat_type as u8]],
+        bump
+    )]
+    pub threat_type_index: Account<'info, ThreatTypeIndex>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ThreatOfType::INIT_SPACE,
+        seeds = [
+            b"threat_of_type".as_ref(),
+            &[threat_type as u8],
+            threat_type_index.count.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub threat_of_type: Account<'info, ThreatOfType>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + RateLimit::INIT_SPACE,
+        seeds = [b"rate_limit", authority.key().as_ref()],
+        bump
+    )]
+    pub rate_limit: Account<'info, RateLimit>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConfirmThreat<'info> {
+    #[account(mut)]
+    pub threat: Account<'info, Threat>,
+    
+    /// Confirmer's registration in agent-coordinator
+    #[account(
+        seeds = [b"agent", authority.key().as_ref()],
+        bump = agent_registration.bump,
+        seeds::program = agent_coordinator::ID
+    )]
+    pub agent_registration: Account<'info, AgentRegistration>,
+    
+    #[account(mut, seeds = [b"threat_counter"], bump = threat_counter.bump)]
+    pub threat_counter: Account<'info, ThreatCounter>,
+    
+    pub coordinator_program: Program<'info, AgentCoordinator>,
+    
+    /// CHECK: Coordination PDA created by agent-coordinator on auto-coordination
+    #[account(mut)]
+    pub coordination: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Initiation receipt PDA created by agent-coordinator on auto-coordination
+    #[account(mut)]
+    pub initiation_receipt: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Validated by agent-coordinator during the CPI
+    #[account(mut)]
+    pub swarm_registry: Option<UncheckedAccount<'info>>,
+    
+    #[account(seeds = [b"swarm_config"], bump = swarm_config.bump, seeds::program = agent_coordinator::ID)]
+    pub swarm_config: Option<Account<'info, SwarmConfig>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Option<Program<'info, System>>,
+}
+
+#[derive(Accounts)]
+pub struct MarkFalsePositive<'info> {
+    #[account(mut)]
+    pub threat: Account<'info, Threat>,
+    
+    #[account(mut, seeds = [b"threat_counter"], bump = threat_counter.bump)]
+    pub threat_counter: Account<'info, ThreatCounter>,
+    
+    pub coordinator_program: Program<'info, AgentCoordinator>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateThreatStatus<'info> {
+    #[account(mut)]
+    pub threat: Account<'info, Threat>,
+    
+    #[account(
+        mut,
+        seeds = [b"threat_counter"],
+        bump = threat_counter.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub threat_counter: Account<'info, ThreatCounter>,
+    
+    pub coordinator_program: Program<'info, AgentCoordinator>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(address: Pubkey)]
+pub struct AddToWatchlist<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + WatchlistEntry::INIT_SPACE,
+        seeds = [b"watchlist", address.as_ref()],
+        bump
+    )]
+    pub watchlist_entry: Account<'info, WatchlistEntry>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CheckWatchlist<'info> {
+    pub watchlist_entry: Account<'info, WatchlistEntry>,
+}
+
+#[derive(Accounts)]
+pub struct SetConfirmationReward<'info> {
+    #[account(
+        mut,
+        seeds = [b"threat_counter"],
+        bump = threat_counter.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub threat_counter: Account<'info, ThreatCounter>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DeactivateWatchlist<'info> {
+    #[account(mut)]
+    pub watchlist_entry: Account<'info, WatchlistEntry>,
+    
+    #[account(seeds = [b"threat_counter"], bump = threat_counter.bump)]
+    pub threat_counter: Account<'info, ThreatCounter>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EscalateSeverity<'info> {
+    #[account(mut)]
+    pub threat: Account<'info, Threat>,
+    
+    #[account(
+        mut,
+        seeds = [b"threat_counter"],
+        bump = threat_counter.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub threat_counter: Account<'info, ThreatCounter>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(coordination_id: u64)]
+pub struct LinkResponse<'info> {
+    #[account(mut)]
+    pub threat: Account<'info, Threat>,
+    
+    #[account(
+        seeds = [b"coordination", coordination_id.to_le_bytes().as_ref()],
+        bump = coordination.bump,
+        seeds::program = agent_coordinator::ID,
+        constraint = coordination.threat_id == threat.threat_id @ ErrorCode::CoordinationThreatMismatch,
+        constraint = coordination.initiator == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub coordination: Account<'info, Coordination>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct BatchConfirmThreat<'info> {
+    #[account(mut)]
+    pub threat: Account<'info, Threat>,
+    
+    #[account(mut, seeds = [b"threat_counter"], bump = threat_counter.bump)]
+    pub threat_counter: Account<'info, ThreatCounter>,
+    
+    pub coordinator_program: Program<'info, AgentCoordinator>,
+    
+    /// CHECK: Coordination PDA created by agent-coordinator on auto-coordination
+    #[account(mut)]
+    pub coordination: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Initiation receipt PDA created by agent-coordinator on auto-coordination
+    #[account(mut)]
+    pub initiation_receipt: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Validated by agent-coordinator during the CPI
+    #[account(mut)]
+    pub swarm_registry: Option<UncheckedAccount<'info>>,
+    
+    #[account(seeds = [b"swarm_config"], bump = swarm_config.bump, seeds::program = agent_coordinator::ID)]
+    pub swarm_config: Option<Account<'info, SwarmConfig>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Option<Program<'info, System>>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateWatchlistRisk<'info> {
+    #[account(mut)]
+    pub watchlist_entry: Account<'info, WatchlistEntry>,
+    
+    #[account(seeds = [b"threat_counter"], bump = threat_counter.bump)]
+    pub threat_counter: Account<'info, ThreatCounter>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct NeutralizeThreat<'info> {
+    #[account(mut)]
+    pub threat: Account<'info, Threat>,
+    
+    /// Recovering agent's registration in agent-coordinator
+    #[account(
+        seeds = [b"agent", authority.key().as_ref()],
+        bump = agent_registration.bump,
+        seeds::program = agent_coordinator::ID
+    )]
+    pub agent_registration: Account<'info, AgentRegistration>,
+    
+    #[account(mut, seeds = [b"threat_counter"], bump = threat_counter.bump)]
+    pub threat_counter: Account<'info, ThreatCounter>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct BatchCheckWatchlist<'info> {
+    // Entries come in remaining_accounts; the generated CPI client needs at
+    // least one 'info account, and the system program is always at hand
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyThreatEvidence<'info> {
+    pub threat: Account<'info, Threat>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReopenThreat<'info> {
+    #[account(mut)]
+    pub threat: Account<'info, Threat>,
+    
+    #[account(
+        seeds = [b"threat_counter"],
+        bump = threat_counter.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub threat_counter: Account<'info, ThreatCounter>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AgeThreat<'info> {
+    #[account(mut)]
+    pub threat: Account<'info, Threat>,
+    
+    #[account(mut, seeds = [b"threat_counter"], bump = threat_counter.bump)]
+    pub threat_counter: Account<'info, ThreatCounter>,
+}
+
+#[derive(Accounts)]
+pub struct GetThreatStats<'info> {
+    #[account(seeds = [b"threat_counter"], bump = threat_counter.bump)]
+    pub threat_counter: Account<'info, ThreatCounter>,
+}
+
+#[derive(Accounts)]
+#[instruction(primary_threat_id: u64)]
+pub struct MergeThreats<'info> {
+    #[account(
+        mut,
+        seeds = [b"threat", primary_threat_id.to_le_bytes().as_ref()],
+        bump = primary_threat.bump
+    )]
+    pub primary_threat: Account<'info, Threat>,
+    
+    #[account(mut)]
+    pub secondary_threat: Account<'info, Threat>,
+    
+    #[account(
+        mut,
+        seeds = [b"threat_counter"],
+        bump = threat_counter.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub threat_counter: Account<'info, ThreatCounter>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetConsensusSeverity<'info> {
+    pub threat: Account<'info, Threat>,
+}
+
+#[derive(Accounts)]
+pub struct FundThreatBounty<'info> {
+    #[account(mut)]
+    pub threat: Account<'info, Threat>,
+    
+    #[account(
+        init_if_needed,
+        payer = funder,
+        space = 8 + BountyContribution::INIT_SPACE,
+        seeds = [b"bounty_contribution", threat.key().as_ref(), funder.key().as_ref()],
+        bump
+    )]
+    pub bounty_contribution: Account<'info, BountyContribution>,
+    
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RefundThreatBounty<'info> {
+    #[account(mut)]
+    pub threat: Account<'info, Threat>,
+    
+    #[account(
+        mut,
+        close = funder,
+        seeds = [b"bounty_contribution", threat.key().as_ref(), funder.key().as_ref()],
+        bump = bounty_contribution.bump,
+        has_one = funder @ ErrorCode::Unauthorized
+    )]
+    pub bounty_contribution: Account<'info, BountyContribution>,
+    
+    #[account(mut)]
+    pub funder: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimThreatBounty<'info> {
+    #[account(mut)]
+    pub threat: Account<'info, Threat>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct NeutralizeFromCoordination<'info> {
+    #[account(
+        mut,
+        seeds = [b"threat", coordination.threat_id.to_le_bytes().as_ref()],
+        bump = threat.bump
+    )]
+    pub threat: Account<'info, Threat>,
+    
+    #[account(
+        seeds = [b"coordination", coordination.coordination_id.to_le_bytes().as_ref()],
+        bump = coordination.bump,
+        seeds::program = agent_coordinator::ID
+    )]
+    pub coordination: Account<'info, Coordination>,
+    
+    /// agent-coordinator's swarm registry PDA, proving the call came from that program
+    #[account(seeds = [b"swarm"], bump, seeds::program = agent_coordinator::ID)]
+    pub swarm_registry: Signer<'info>,
+    
+    #[account(mut, seeds = [b"threat_counter"], bump = threat_counter.bump)]
+    pub threat_counter: Account<'info, ThreatCounter>,
+}
+
+// ============== HELPERS ==============
+
+/// Whether a watchlist entry is active and not past its expiry
+fn is_watched(entry: &WatchlistEntry, now: i64) -> bool {
+    entry.active && !matches!(entry.expires_at, Some(expiry) if now >= expiry)
+}
+
+/// Legal threat status moves; Neutralized, FalsePositive and Merged are terminal
+fn can_transition(old: ThreatStatus, new: ThreatStatus) -> bool {
+    use ThreatStatus::*;
+    matches!(
+        (old, new),
+        (Active, Confirmed | FalsePositive | UnderInvestigation | Escalated | Merged)
+            | (UnderInvestigation, Active | Confirmed | FalsePositive | Merged)
+            | (Confirmed, Escalated | Neutralized | FalsePositive | UnderInvestigation | Merged)
+            | (Escalated, Neutralized | FalsePositive | Merged)
+    )
+}
+
+/// Only a live threat can absorb another's confirmations
+fn can_absorb_merge(status: ThreatStatus) -> bool {
+    matches!(
+        status,
+        ThreatStatus::Active | ThreatStatus::UnderInvestigation | ThreatStatus::Confirmed
+    )
+}
+
+/// Carry `confirmers` over to `primary`, skipping its detector and existing
+/// confirmers and stopping at MAX_CONFIRMERS. Returns those transferred
+fn merge_confirmers(primary: &mut Threat, confirmers: &[Pubkey]) -> Vec<Pubkey> {
+    let mut transferred: Vec<Pubkey> = vec![];
+    for confirmer in confirmers {
+        if primary.confirmed_by.len() >= MAX_CONFIRMERS {
+            break;
+        }
+        if *confirmer != primary.detected_by && !primary.confirmed_by.contains(confirmer) {
+            primary.confirmed_by.push(*confirmer);
+            transferred.push(*confirmer);
+        }
+    }
+    transferred
+}
+
+/// Add a funder's deposit to both the threat's pool and their own record
+fn record_bounty_contribution(
+    threat: &mut Threat,
+    contribution: &mut BountyContribution,
+    amount: u64,
+) -> Result<()> {
+    threat.bounty_amount = threat
+        .bounty_amount
+        .checked_add(amount)
+        .ok_or(ErrorCode::BountyOverflow)?;
+    contribution.amount = contribution
+        .amount
+        .checked_add(amount)
+        .ok_or(ErrorCode::BountyOverflow)?;
+    Ok(())
+}
+
+/// Split the bounty held in `threat_info` between the detector and confirmers,
+/// whose accounts lead `recipient_infos` in that order. Returns the recipients and share
+fn pay_bounty<'info>(
+    threat: &mut Threat,
+    threat_info: &AccountInfo<'info>,
+    recipient_infos: &[AccountInfo<'info>],
+) -> Result<(Vec<Pubkey>, u64)> {
+    let mut recipients = vec![threat.detected_by];
+    recipients.extend(threat.confirmed_by.iter().copied());
+    require!(
+        recipient_infos.len() >= recipients.len()
+            && recipients
+                .iter()
+                .zip(recipient_infos)
+                .all(|(recipient, info)| info.key() == *recipient),
+        ErrorCode::MissingBountyRecipients
+    );
+
+    let total = threat.bounty_amount;
+    let (share, remainder) = bounty_shares(total, recipients.len());
+
+    threat.bounty_claimed = true;
+    threat.bounty_amount = 0;
+    threat_info.sub_lamports(total)?;
+    for (i, info) in recipient_infos.iter().take(recipients.len()).enumerate() {
+        let amount = if i == 0 { share + remainder } else { share };
+        info.add_lamports(amount)?;
+    }
+    Ok((recipients, share))
+}
+
+/// Move a funder's contribution back out of a dismissed or merged threat
+fn refund_contribution<'info>(
+    threat: &mut Threat,
+    contribution: &mut BountyContribution,
+    threat_info: &AccountInfo<'info>,
+    funder_info: &AccountInfo<'info>,
+) -> Result<u64> {
+    require!(
+        matches!(threat.status, ThreatStatus::FalsePositive | ThreatStatus::Merged),
+        ErrorCode::BountyNotRefundable
+    );
+    require!(!threat.bounty_claimed, ErrorCode::BountyAlreadyClaimed);
+
+    let amount = contribution.amount;
+    require!(amount > 0, ErrorCode::NoBounty);
+    threat.bounty_amount = threat.bounty_amount.saturating_sub(amount);
+    contribution.amount = 0;
+    threat_info.sub_lamports(amount)?;
+    funder_info.add_lamports(amount)?;
+    Ok(amount)
+}
+
+/// Equal bounty share per recipient, and the remainder owed to the detector
+fn bounty_shares(total: u64, recipient_count: usize) -> (u64, u64) {
+    let share = total / recipient_count as u64;
+    (share, total - share * recipient_count as u64)
+}
+
+/// Capabilities qualified to confirm a threat type; holding any one suffices
+fn required_confirm_capability(threat_type: ThreatType) -> &'static [Capability] {
+    match threat_type {
+        ThreatType::RugPull
+        | ThreatType::UnauthorizedMint
+        | ThreatType::FlashLoanAttack => {
+            &[Capability::ContractAnalysis, Capability::TransactionMonitoring]
+        }
+        ThreatType::Honeypot => &[Capability::ContractAnalysis],
+        ThreatType::PhishingContract => &[Capability::ContractAnalysis, Capability::ThreatDetection],
+        ThreatType::SuspiciousTransfer => {
+            &[Capability::TransactionMonitoring, Capability::ActorTracking]
+        }
+        ThreatType::PriceManipulation => {
+            &[Capability::TransactionMonitoring, Capability::RiskPrediction]
+        }
+        ThreatType::SandwichAttack => &[Capability::TransactionMonitoring],
+        ThreatType::DrainAttack => &[Capability::TransactionMonitoring, Capability::ThreatDetection],
+        ThreatType::Unknown => &[Capability::ThreatDetection, Capability::ContractAnalysis],
+    }
+}
+
+/// Only active agents holding a capability relevant to the threat type may confirm it
+fn require_confirmer_capability(agent: &AgentRegistration, threat_type: ThreatType) -> Result<()> {
+    require!(agent.active, ErrorCode::NotAuthorizedConfirmer);
+    require!(
+        required_confirm_capability(threat_type)
+            .iter()
+            .any(|capability| agent.capabilities.contains(capability)),
+        ErrorCode::WrongCapabilityToConfirm
+    );
+    Ok(())
+}
+
+/// False-positive votes needed to bury a threat: serious and already-confirmed
+/// threats take more votes than minor unconfirmed ones
+fn false_positive_threshold(severity: u8, status: ThreatStatus) -> u8 {
+    let severity_extra = match severity {
+        0..=49 => 0,
+        50..=74 => 1,
+        _ => 2,
+    };
+    let status_extra = match status {
+        ThreatStatus::Confirmed | ThreatStatus::Escalated => 2,
+        _ => 0,
+    };
+    3 + severity_extra + status_extra
+}
+
+/// Confirmations needed to auto-escalate, scaled from the configured base:
+/// credible severe threats escalate faster, minor ones need more scrutiny
+fn min_confirmations_for_severity(severity: u8, required_confirmations: u8) -> usize {
+    let base = required_confirmations as usize;
+    match severity {
+        0..=24 => base + 2,
+        25..=74 => base,
+        _ => base.saturating_sub(1).max(1),
+    }
+}
+
+/// Crowd-sourced severity: the mean of every severity vote cast on the threat
+fn consensus_severity(threat: &Threat) -> u8 {
+    threat
+        .severity_sum
+        .checked_div(threat.severity_votes as u16)
+        .unwrap_or(threat.severity as u16) as u8
+}
+
+/// Record `voter`'s false-positive vote: once per voter, never the detector,
+/// and never past the voter list's capacity
+fn record_false_positive_vote(threat: &mut Threat, voter: Pubkey) -> Result<()> {
+    require!(threat.detected_by != voter, ErrorCode::CannotVoteOwn);
+    require!(
+        !threat.false_positive_voters.contains(&voter),
+        ErrorCode::AlreadyVotedFalsePositive
+    );
+    require!(
+        threat.false_positive_voters.len() < MAX_FALSE_POSITIVE_VOTERS,
+        ErrorCode::TooManyFalsePositiveVoters
+    );
+
+    threat.false_positive_voters.push(voter);
+    threat.false_positive_votes += 1;
+    Ok(())
+}
+
+/// Whether a status counts toward the counter's live-threat aggregates
+fn is_live(status: ThreatStatus) -> bool {
+    matches!(
+        status,
+        ThreatStatus::Active | ThreatStatus::Confirmed | ThreatStatus::Escalated
+    )
+}
+
+/// Move a threat to `new_status`, keeping the counter's live-threat aggregates in step
+fn set_threat_status(counter: &mut ThreatCounter, threat: &mut Threat, new_status: ThreatStatus) {
+    match (is_live(threat.status), is_live(new_status)) {
+        (true, false) => {
+            counter.active_threats = counter.active_threats.saturating_sub(1);
+            counter.active_severity_sum =
+                counter.active_severity_sum.saturating_sub(threat.severity as u64);
+        }
+        (false, true) => {
+            counter.active_threats += 1;
+            counter.active_severity_sum += threat.severity as u64;
+        }
+        _ => {}
+    }
+    threat.status = new_status;
+}
+
+/// Change a threat's severity, keeping the live severity sum in step
+fn set_threat_severity(counter: &mut ThreatCounter, threat: &mut Threat, new_severity: u8) {
+    if is_live(threat.status) {
+        counter.active_severity_sum = counter
+            .active_severity_sum
+            .saturating_sub(threat.severity as u64)
+            + new_severity as u64;
+    }
+    threat.severity = new_severity;
+}
+
+/// Count a threat into the counter's status totals, only on its first entry
+/// into that status so reopen round trips can't inflate the stats
+fn count_status_entry(counter: &mut ThreatCounter, threat: &mut Threat, status: ThreatStatus) {
+    match status {
+        ThreatStatus::Confirmed if !threat.counted_confirmed => {
+            threat.counted_confirmed = true;
+            counter.total_confirmed += 1;
+        }
+        ThreatStatus::FalsePositive if !threat.counted_false_positive => {
+            threat.counted_false_positive = true;
+            counter.total_false_positive += 1;
+        }
+        _ => {}
+    }
+}
+
+/// Histogram bucket for a severity: 0-20, 21-40, 41-60, 61-80, 81-100
+fn severity_bucket(severity: u8) -> usize {
+    match severity {
+        0..=20 => 0,
+        21..=40 => 1,
+        41..=60 => 2,
+        61..=80 => 3,
+        _ => 4,
+    }
+}
+
+/// Escalate, reward and bump severity once confirmations cross their thresholds.
+/// `reward_accounts` are the confirmers' AgentRegistrations in `confirmed_by` order.
+/// Returns whether the threat escalated to Confirmed
+fn apply_confirmation_thresholds<'info>(
+    threat: &mut Account<'info, Threat>,
+    previous_confirmations: usize,
+    threat_counter: &mut Account<'info, ThreatCounter>,
+    coordinator_program: &Program<'info, AgentCoordinator>,
+    reward_accounts: &'info [AccountInfo<'info>],
+) -> Result<bool> {
+    let mut escalated = false;
+
+    // Auto-escalate once confirmations meet the severity-scaled bar
+    if threat.confirmed_by.len()
+        >= min_confirmations_for_severity(threat.severity, threat_counter.required_confirmations)
+        && threat.status == ThreatStatus::Active
+    {
+        escalated = true;
+        set_threat_status(threat_counter, threat, ThreatStatus::Confirmed);
+        emit!(ThreatEscalated {
+            threat_id: threat.threat_id,
+            new_status: ThreatStatus::Confirmed,
+            confirmations: threat.confirmed_by.len() as u8,
+            consensus_severity: consensus_severity(threat),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        // Reward the confirmers now; clawed back if this turns out a false positive
+        let reward = threat_counter.confirmation_reward;
+        if reward > 0 {
+            let amounts = vec![reward; threat.confirmed_by.len()];
+            threat.reward_deltas = adjust_confirmer_reputation(
+                threat_counter,
+                coordinator_program,
+                reward_accounts,
+                &threat.confirmed_by,
+                &amounts,
+                false,
+            )?;
+            threat.confirmer_reward = reward;
+
+            emit!(ConfirmersRewarded {
+                threat_id: threat.threat_id,
+                confirmers: threat.confirmed_by.clone(),
+                amount: reward,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+    }
+
+    // Broad agreement on a confirmed threat raises its severity once
+    if previous_confirmations < AUTO_SEVERITY_CONFIRMATIONS
+        && threat.confirmed_by.len() >= AUTO_SEVERITY_CONFIRMATIONS
+        && threat.status == ThreatStatus::Confirmed
+    {
+        raise_severity(threat_counter, threat, AUTO_SEVERITY_BUMP)?;
+    }
+
+    Ok(escalated)
+}
+
+/// Accounts shared by the single and batch confirm paths for auto-coordination
+struct AutoCoordinationAccounts<'a, 'info> {
+    coordinator_program: &'a Program<'info, AgentCoordinator>,
+    threat: &'a Account<'info, Threat>,
+    authority: &'a Signer<'info>,
+    coordination: &'a Option<UncheckedAccount<'info>>,
+    initiation_receipt: &'a Option<UncheckedAccount<'info>>,
+    swarm_registry: &'a Option<UncheckedAccount<'info>>,
+    swarm_config: &'a Option<Account<'info, SwarmConfig>>,
+    system_program: &'a Option<Program<'info, System>>,
+}
+
+/// CPI into agent-coordinator to open a response coordination for a newly
+/// confirmed threat, initiated by the confirming agent. Returns the coordination id,
+/// or None when the accounts are missing or the swarm is paused
+fn trigger_auto_coordination<'info>(
+    threat_id: u64,
+    severity: u8,
+    accounts: AutoCoordinationAccounts<'_, 'info>,
+) -> Result<Option<u64>> {
+    let (
+        Some(coordination),
+        Some(initiation_receipt),
+        Some(swarm_registry),
+        Some(swarm_config),
+        Some(system_program),
+    ) = (
+        accounts.coordination,
+        accounts.initiation_receipt,
+        accounts.swarm_registry,
+        accounts.swarm_config,
+        accounts.system_program,
+    )
+    else {
+        return skip_auto_coordination(threat_id, AutoCoordinationSkip::AccountsMissing);
+    };
+
+    let Some(coordination_id) = next_auto_coordination_id(swarm_registry)? else {
+        return skip_auto_coordination(threat_id, AutoCoordinationSkip::SwarmPaused);
+    };
+    let params = &swarm_config.params;
+    let urgency = urgency_from_severity(severity);
+    let expiry_seconds =
+        AUTO_COORDINATION_EXPIRY.clamp(params.min_expiry_seconds, params.max_expiry_seconds);
+
+    // One auto-coordination per threat and initiator
+    let mut idempotency_key = [0u8; 16];
+    idempotency_key[..8].copy_from_slice(&threat_id.to_le_bytes());
+
+    agent_coordinator::cpi::initiate_coordination(
+        CpiContext::new(
+            accounts.coordinator_program.to_account_info(),
+            agent_coordinator::cpi::accounts::InitiateCoordination {
+                coordination: coordination.to_account_info(),
+                initiation_receipt: initiation_receipt.to_account_info(),
+                swarm_registry: swarm_registry.to_account_info(),
+                swarm_config: swarm_config.to_account_info(),
+                threat: Some(accounts.threat.to_account_info()),
+                authority: accounts.authority.to_account_info(),
+                system_program: system_program.to_account_info(),
+            },
+        ),
+        threat_id,
+        vec![
+            Capability::ThreatDetection,
+            Capability::TransactionMonitoring,
+            Capability::FundRecovery,
+        ],
+        format!(
+            "Automated response to confirmed threat #{}: contain, monitor and recover",
+            threat_id
+        ),
+        Some(urgency),
+        expiry_seconds,
+        None,
+        params.min_quorum,
+        10,
+        idempotency_key,
+        false,
+    )?;
+
+    emit!(AutoCoordinationTriggered {
+        threat_id,
+        coordination_id,
+        urgency,
+        initiator: accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(Some(coordination_id))
+}
+
+/// Id the next coordination will take, or None while the swarm is paused
+fn next_auto_coordination_id(swarm_registry: &AccountInfo) -> Result<Option<u64>> {
+    if *swarm_registry.owner != agent_coordinator::ID {
+        return Err(anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram.into());
+    }
+    let swarm = SwarmRegistry::try_deserialize(&mut &swarm_registry.try_borrow_data()?[..])?;
+    Ok((!swarm.paused).then_some(swarm.total_coordinations))
+}
+
+fn skip_auto_coordination(threat_id: u64, reason: AutoCoordinationSkip) -> Result<Option<u64>> {
+    emit!(AutoCoordinationSkipped {
+        threat_id,
+        reason,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(None)
+}
+
+/// Add `delta` to a threat's severity, capped at 100
+fn raise_severity(counter: &mut ThreatCounter, threat: &mut Threat, delta: u8) -> Result<()> {
+    let old_severity = threat.severity;
+    let new_severity = std::cmp::min(100, old_severity.saturating_add(delta));
+    require!(new_severity <= 100, ErrorCode::InvalidSeverity);
+
+    set_threat_severity(counter, threat, new_severity);
+
+    emit!(SeverityEscalated {
+        threat_id: threat.threat_id,
+        old_severity,
+        new_severity,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// CPI into agent-coordinator to adjust each confirmer's reputation, signed by the
+/// threat counter PDA. `agent_accounts` must follow the order of `confirmers`
+fn adjust_confirmer_reputation<'info>(
+    threat_counter: &Account<'info, ThreatCounter>,
+    coordinator_program: &Program<'info, AgentCoordinator>,
+    agent_accounts: &'info [AccountInfo<'info>],
+    confirmers: &[Pubkey],
+    amounts: &[u8],
+    clawback: bool,
+) -> Result<Vec<u8>> {
+    require!(
+        agent_accounts.len() == confirmers.len(),
+        ErrorCode::MissingConfirmerAccounts
+    );
+
+    let signer_seeds: &[&[&[u8]]] = &[&[b"threat_counter", &[threat_counter.bump]]];
+
+    let mut applied = Vec::with_capacity(confirmers.len());
+    for ((info, confirmer), amount) in agent_accounts.iter().zip(confirmers).zip(amounts) {
+        let registration = Account::<AgentRegistration>::try_from(info)?;
+        require_keys_eq!(
+            registration.agent_id,
+            *confirmer,
+            ErrorCode::MissingConfirmerAccounts
+        );
+
+        let delta = agent_coordinator::cpi::apply_confirmation_reward(
+            CpiContext::new_with_signer(
+                coordinator_program.to_account_info(),
+                agent_coordinator::cpi::accounts::ApplyConfirmationReward {
+                    agent_registration: info.clone(),
+                    threat_counter: threat_counter.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            *amount,
+            clawback,
+        )?;
+        applied.push(delta.get());
+    }
+
+    Ok(applied)
+}
+
+/// Confirmers paid at escalation; `confirmed_by` only grows, so they're its prefix
+fn rewarded_confirmers(threat: &Threat) -> &[Pubkey] {
+    let count = threat.reward_deltas.len().min(threat.confirmed_by.len());
+    &threat.confirmed_by[..count]
+}
+
+/// Reverse any escalation reward previously granted to a threat's confirmers.
+/// Only those rewarded at escalation are clawed back, each by exactly the delta
+/// it received; `agent_accounts` follow that set
+fn clawback_confirmer_reward<'info>(
+    threat: &mut Account<'info, Threat>,
+    threat_counter: &Account<'info, ThreatCounter>,
+    coordinator_program: &Program<'info, AgentCoordinator>,
+    agent_accounts: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    if threat.confirmer_reward == 0 {
+        return Ok(());
+    }
+
+    let rewarded = rewarded_confirmers(threat).to_vec();
+    let amounts = threat.reward_deltas[..rewarded.len()].to_vec();
+    adjust_confirmer_reputation(
+        threat_counter,
+        coordinator_program,
+        agent_accounts,
+        &rewarded,
+        &amounts,
+        true,
+    )?;
+    threat.confirmer_reward = 0;
+    threat.reward_deltas = vec![];
+
+    emit!(ConfirmerRewardsClawedBack {
+        threat_id: threat.threat_id,
+        confirmers: rewarded,
+        amounts,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// ============== STATE ==============
+
+#[account]
+#[derive(InitSpace)]
+pub struct ThreatCounter {
+    pub count: u64,
+    pub authority: Pubkey,
+    pub confirmation_reward: u8, // reputation bonus per confirmer on escalation
+    pub required_confirmations: u8, // base confirmations for auto-escalation
+    pub aging_window_seconds: i64, // how long a threat may stay Active unconfirmed
+    pub aging_severity_decay: u8, // severity removed when a threat is aged
+    pub total_confirmed: u64, // threats that reached Confirmed
+    pub total_false_positive: u64, // threats that reached FalsePositive
+    pub severity_sum: u64, // sum of severities as registered
+    pub active_threats: u64, // threats currently Active, Confirmed or Escalated
+    pub active_severity_sum: u64, // current severities of those threats
+    pub severity_buckets: [u64; 5], // registrations per 20-point severity band
+    pub rate_limit_window_seconds: i64, // window for rate_limit_max
+    pub rate_limit_max: u16, // registrations allowed per authority per window
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Threat {
+    pub threat_id: u64,
+    pub threat_type: ThreatType,
+    pub severity: u8, // 0-100
+    pub target_address: Option<Pubkey>,
+    pub target_kind: TargetKind, // how responders should treat target_address
+    #[max_len(500)]
+    pub description: String,
+    pub evidence_hash: [u8; 32],
+    #[max_len(200)]
+    pub evidence_uri: String, // IPFS/Arweave CID or URL of the evidence
+    pub detected_at: i64,
+    pub detected_by: Pubkey,
+    pub status: ThreatStatus,
+    #[max_len(10)]
+    pub confirmed_by: Vec<Pubkey>,
+    pub false_positive_votes: u8,
+    #[max_len(10)]
+    pub false_positive_voters: Vec<Pubkey>,
+    pub confirmer_reward: u8, // bonus granted on escalation, 0 once clawed back
+    #[max_len(10)]
+    pub reward_deltas: Vec<u8>, // reputation actually added to each leading confirmed_by entry
+    pub response_coordination_id: Option<u64>, // coordination spun up in response
+    pub auto_coordinate: bool, // open a coordination automatically on escalation
+    pub recovery_tx_hash: Option<[u8; 32]>, // set when neutralized by recovery
+    pub recovered_amount: u64,
+    pub last_aged: i64, // 0 until first aged
+    pub merged_into: Option<u64>, // primary threat id once Merged
+    pub severity_sum: u16, // detector's severity plus each confirmer's estimate
+    pub severity_votes: u8,
+    pub bounty_amount: u64, // lamports held in this PDA beyond rent until claimed
+    pub bounty_claimed: bool,
+    pub counted_confirmed: bool, // already in the counter's total_confirmed
+    pub counted_false_positive: bool, // already in the counter's total_false_positive
+    pub bump: u8,
+}
+
+/// One funder's running contribution to a threat's bounty
+#[account]
+#[derive(InitSpace)]
+pub struct BountyContribution {
+    pub threat_id: u64,
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub bump: u8,
+}
+
+/// Threat registrations by one authority within the current window
+#[account]
+#[derive(InitSpace)]
+pub struct RateLimit {
+    pub authority: Pubkey,
+    pub window_start: i64,
+    pub count_in_window: u16,
+    pub bump: u8,
+}
+
+/// Number of threats registered per ThreatType
+#[account]
+#[derive(InitSpace)]
+pub struct ThreatTypeIndex {
+    pub threat_type: ThreatType,
+    pub count: u64,
+    pub bump: u8,
+}
+
+/// Maps a type-local index to the global threat id
+#[account]
+#[derive(InitSpace)]
+pub struct ThreatOfType {
+    pub threat_type: ThreatType,
+    pub type_local_id: u64,
+    pub threat_id: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct WatchlistEntry {
+    pub address: Pubkey,
+    pub category: WatchlistCategory, // fixed offset so clients can memcmp-filter on it
+    #[max_len(200)]
+    pub reason: String,
+    pub linked_threat_id: Option<u64>,
+    pub risk_score: u8, // 0-100
+    pub added_at: i64,
+    pub added_by: Pubkey,
+    pub active: bool,
+    pub deactivated_at: i64, // 0 while active
+    pub expires_at: Option<i64>, // None watches indefinitely
+    pub bump: u8,
+}
+
+// ============== TYPES ==============
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum ThreatType {
+    RugPull,
+    Honeypot,
+    PhishingContract,
+    SuspiciousTransfer,
+    PriceManipulation,
+    UnauthorizedMint,
+    FlashLoanAttack,
+    SandwichAttack,
+    DrainAttack,
+    Unknown,
+}
+
+/// What kind of account a threat's target is
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum TargetKind {
+    Wallet,
+    Program,
+    TokenMint,
+    Unspecified,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum ThreatStatus {
+    Active,
+    Confirmed,
+    Neutralized,
+    FalsePositive,
+    UnderInvestigation,
+    Escalated,
+    Merged, // duplicate folded into another threat
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AutoCoordinationSkip {
+    AccountsMissing,
+    SwarmPaused,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum WatchlistCategory {
+    Scammer,
+    Mixer,
+    Sanctioned,
+    CompromisedContract,
+    KnownExploiter,
+    Other,
+}
+
+/// Aggregate threat statistics read from the counter
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ThreatStats {
+    pub total_threats: u64,
+    pub total_confirmed: u64,
+    pub total_false_positive: u64,
+    pub severity_sum: u64,
+    pub mean_severity: u8,
+}
+
+/// Result of a watchlist lookup
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct WatchlistStatus {
+    pub active: bool,
+    pub risk_score: u8,
+    pub category: WatchlistCategory,
+    pub linked_threat_id: Option<u64>,
+}
+
+// ============== EVENTS ==============
+
+#[event]
+pub struct ThreatRegistered {
+    pub threat_id: u64,
+    pub threat_type: ThreatType,
+    pub severity: u8,
+    pub target_address: Option<Pubkey>,
+    pub target_kind: TargetKind,
+    pub detected_by: Pubkey,
+    pub evidence_uri: String,
+    pub type_local_id: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ThreatConfirmed {
+    pub threat_id: u64,
+    pub confirmed_by: Pubkey,
+    pub total_confirmations: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ThreatEscalated {
+    pub threat_id: u64,
+    pub new_status: ThreatStatus,
+    pub confirmations: u8,
+    pub consensus_severity: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ThreatStatusChanged {
+    pub threat_id: u64,
+    pub old_status: ThreatStatus,
+    pub new_status: ThreatStatus,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AddressWatchlisted {
+    pub address: Pubkey,
+    pub category: WatchlistCategory,
+    pub linked_threat_id: Option<u64>,
+    pub added_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfirmersRewarded {
+    pub threat_id: u64,
+    pub confirmers: Vec<Pubkey>,
+    pub amount: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfirmerRewardsClawedBack {
+    pub threat_id: u64,
+    pub confirmers: Vec<Pubkey>,
+    pub amounts: Vec<u8>, // per confirmer, what its escalation reward actually added
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AddressDelisted {
+    pub address: Pubkey,
+    pub delisted_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FalsePositiveVoted {
+    pub threat_id: u64,
+    pub voter: Pubkey,
+    pub total_votes: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SeverityEscalated {
+    pub threat_id: u64,
+    pub old_severity: u8,
+    pub new_severity: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ThreatResponseLinked {
+    pub threat_id: u64,
+    pub coordination_id: u64,
+    pub linked_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BatchThreatConfirmed {
+    pub threat_id: u64,
+    pub confirmers: Vec<Pubkey>,
+    pub total_confirmations: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WatchlistRiskUpdated {
+    pub address: Pubkey,
+    pub old_score: u8,
+    pub new_score: u8,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AutoCoordinationTriggered {
+    pub threat_id: u64,
+    pub coordination_id: u64,
+    pub urgency: Urgency,
+    pub initiator: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AutoCoordinationSkipped {
+    pub threat_id: u64,
+    pub reason: AutoCoordinationSkip,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WatchlistExtended {
+    pub address: Pubkey,
+    pub old_expiry: Option<i64>,
+    pub new_expiry: i64,
+    pub extended_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ThreatNeutralized {
+    pub threat_id: u64,
+    pub old_status: ThreatStatus,
+    pub recovery_tx_hash: [u8; 32],
+    pub recovered_amount: u64,
+    pub neutralized_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ThreatEvidenceVerified {
+    pub threat_id: u64,
+    pub verified_by: Pubkey,
+    pub is_valid: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ThreatReopened {
+    pub threat_id: u64,
+    pub reopened_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ThreatsMerged {
+    pub primary_threat_id: u64,
+    pub secondary_threat_id: u64,
+    pub transferred_confirmers: Vec<Pubkey>,
+    pub total_confirmations: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BountyFunded {
+    pub threat_id: u64,
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub total_bounty: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BountyClaimed {
+    pub threat_id: u64,
+    pub recipients: Vec<Pubkey>,
+    pub share: u64,
+    pub total: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BountyRefunded {
+    pub threat_id: u64,
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub remaining_bounty: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ThreatNeutralizedByCoordination {
+    pub threat_id: u64,
+    pub coordination_id: u64,
+    pub old_status: ThreatStatus,
+    pub timestamp: i64,
+}
+
+// ============== ERRORS ==============
 
 #[error_code]
 pub enum ErrorCode {
@@ -395,4 +2448,460 @@ pub enum ErrorCode {
     AlreadyConfirmed,
     #[msg("Unauthorized for this operation")]
     Unauthorized,
+    #[msg("Reward amount must be between 0 and 100")]
+    InvalidReward,
+    #[msg("Confirmer agent registrations missing or out of order")]
+    MissingConfirmerAccounts,
+    #[msg("Watchlist entry is already inactive")]
+    AlreadyInactive,
+    #[msg("Confirmer must be an active agent")]
+    NotAuthorizedConfirmer,
+    #[msg("Cannot vote your own threat a false positive")]
+    CannotVoteOwn,
+    #[msg("Already voted this threat a false positive")]
+    AlreadyVotedFalsePositive,
+    #[msg("Threat has not been confirmed")]
+    NotConfirmed,
+    #[msg("Evidence URI must be non-empty and at most 200 characters")]
+    InvalidEvidenceUri,
+    #[msg("Threat is already linked to a response coordination")]
+    ResponseAlreadyLinked,
+    #[msg("Coordination does not target this threat")]
+    CoordinationThreatMismatch,
+    #[msg("Batch would exceed the maximum number of confirmers")]
+    TooManyConfirmers,
+    #[msg("Every batch confirmer must sign the transaction")]
+    ConfirmerNotSigner,
+    #[msg("Risk score must be between 0 and 100")]
+    InvalidRiskScore,
+    #[msg("Expiry must be in the future and later than the current expiry")]
+    InvalidExpiry,
+    #[msg("Only confirmed or escalated threats can be neutralized")]
+    CannotNeutralize,
+    #[msg("Neutralizer must be an active agent with fund recovery capability")]
+    NotAuthorizedRecoverer,
+    #[msg("Illegal threat status transition")]
+    IllegalStatusTransition,
+    #[msg("Too many addresses in one watchlist check")]
+    TooManyAddresses,
+    #[msg("Evidence exceeds maximum size")]
+    EvidenceTooLarge,
+    #[msg("Threat is not marked as a false positive")]
+    NotFalsePositive,
+    #[msg("Required confirmations must be between 1 and 8")]
+    InvalidConfirmationCount,
+    #[msg("Aging window must be a positive number of seconds")]
+    InvalidAgingWindow,
+    #[msg("Threat is not Active past its aging window")]
+    NotAgeable,
+    #[msg("Confirmer lacks a capability suited to this threat type")]
+    WrongCapabilityToConfirm,
+    #[msg("A threat can't be merged into itself")]
+    CannotMergeIntoSelf,
+    #[msg("Threat registration rate limit exceeded")]
+    RateLimited,
+    #[msg("Rate limit window and maximum must be positive")]
+    InvalidRateLimit,
+    #[msg("Threat has no bounty")]
+    NoBounty,
+    #[msg("Threat bounty has already been claimed")]
+    BountyAlreadyClaimed,
+    #[msg("Threat was dismissed or merged")]
+    ThreatClosed,
+    #[msg("Bounty amount overflow")]
+    BountyOverflow,
+    #[msg("Bounty recipients missing or out of order in remaining accounts")]
+    MissingBountyRecipients,
+    #[msg("Coordination did not execute with full success")]
+    CoordinationNotSuccessful,
+    #[msg("Each confirmer needs exactly one severity vote")]
+    SeverityVoteMismatch,
+    #[msg("Primary threat must be Active, UnderInvestigation or Confirmed")]
+    InvalidMergeTarget,
+    #[msg("Bounties are only refunded from dismissed or merged threats")]
+    BountyNotRefundable,
+    #[msg("Threat has reached the maximum number of false-positive voters")]
+    TooManyFalsePositiveVoters,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn threat(confirmed_by: Vec<Pubkey>) -> Threat {
+        Threat {
+            threat_id: 1,
+            threat_type: ThreatType::RugPull,
+            severity: 60,
+            target_address: None,
+            target_kind: TargetKind::Unspecified,
+            description: String::new(),
+            evidence_hash: [0; 32],
+            evidence_uri: String::new(),
+            detected_at: 0,
+            detected_by: Pubkey::new_unique(),
+            status: ThreatStatus::Active,
+            confirmed_by,
+            false_positive_votes: 0,
+            false_positive_voters: vec![],
+            confirmer_reward: 0,
+            reward_deltas: vec![],
+            response_coordination_id: None,
+            auto_coordinate: false,
+            recovery_tx_hash: None,
+            recovered_amount: 0,
+            last_aged: 0,
+            merged_into: None,
+            severity_sum: 60,
+            severity_votes: 1,
+            bounty_amount: 0,
+            bounty_claimed: false,
+            counted_confirmed: false,
+            counted_false_positive: false,
+            bump: 0,
+        }
+    }
+
+    fn counter() -> ThreatCounter {
+        ThreatCounter {
+            count: 1,
+            authority: Pubkey::new_unique(),
+            confirmation_reward: 5,
+            required_confirmations: 2,
+            aging_window_seconds: 0,
+            aging_severity_decay: 0,
+            total_confirmed: 0,
+            total_false_positive: 0,
+            severity_sum: 60,
+            active_threats: 0,
+            active_severity_sum: 0,
+            severity_buckets: [0; 5],
+            rate_limit_window_seconds: 0,
+            rate_limit_max: 0,
+            bump: 0,
+        }
+    }
+
+    fn keys(n: usize) -> Vec<Pubkey> {
+        (0..n).map(|_| Pubkey::new_unique()).collect()
+    }
+
+    #[test]
+    fn clawback_covers_only_confirmers_rewarded_at_escalation() {
+        let confirmers = keys(3);
+        let mut threat = threat(confirmers[..2].to_vec());
+        threat.confirmer_reward = 5;
+        threat.reward_deltas = vec![5, 2];
+
+        // A late confirmer joins after the escalation reward was paid
+        threat.confirmed_by.push(confirmers[2]);
+
+        assert_eq!(rewarded_confirmers(&threat), &confirmers[..2]);
+    }
+
+    #[test]
+    fn rewarded_confirmers_never_exceeds_confirmed_by() {
+        let mut threat = threat(keys(1));
+        threat.reward_deltas = vec![5; 4];
+
+        assert_eq!(rewarded_confirmers(&threat).len(), 1);
+    }
+
+    #[test]
+    fn bounty_remainder_goes_to_the_detector() {
+        assert_eq!(bounty_shares(1_000, 3), (333, 1));
+        assert_eq!(bounty_shares(900, 3), (300, 0));
+        assert_eq!(bounty_shares(2, 3), (0, 2));
+    }
+
+    #[test]
+    fn merge_dedupes_and_skips_the_detector() {
+        let shared = Pubkey::new_unique();
+        let mut primary = threat(vec![shared]);
+        let secondary = vec![shared, primary.detected_by, Pubkey::new_unique()];
+
+        let transferred = merge_confirmers(&mut primary, &secondary);
+
+        assert_eq!(transferred, vec![secondary[2]]);
+        assert_eq!(primary.confirmed_by, vec![shared, secondary[2]]);
+    }
+
+    #[test]
+    fn merge_stops_at_max_confirmers() {
+        let mut primary = threat(keys(MAX_CONFIRMERS - 1));
+
+        let transferred = merge_confirmers(&mut primary, &keys(3));
+
+        assert_eq!(transferred.len(), 1);
+        assert_eq!(primary.confirmed_by.len(), MAX_CONFIRMERS);
+    }
+
+    #[test]
+    fn merge_targets_and_sources() {
+        assert!(can_absorb_merge(ThreatStatus::Active));
+        assert!(can_absorb_merge(ThreatStatus::UnderInvestigation));
+        assert!(can_absorb_merge(ThreatStatus::Confirmed));
+        assert!(!can_absorb_merge(ThreatStatus::Merged));
+        assert!(!can_absorb_merge(ThreatStatus::FalsePositive));
+        assert!(!can_absorb_merge(ThreatStatus::Neutralized));
+
+        assert!(can_transition(ThreatStatus::Escalated, ThreatStatus::Merged));
+        assert!(!can_transition(ThreatStatus::Merged, ThreatStatus::Active));
+        assert!(!can_transition(ThreatStatus::Neutralized, ThreatStatus::Merged));
+    }
+
+    #[test]
+    fn status_round_trips_count_once() {
+        let (mut counter, mut threat) = (counter(), threat(vec![]));
+
+        count_status_entry(&mut counter, &mut threat, ThreatStatus::FalsePositive);
+        count_status_entry(&mut counter, &mut threat, ThreatStatus::FalsePositive);
+        count_status_entry(&mut counter, &mut threat, ThreatStatus::Confirmed);
+        count_status_entry(&mut counter, &mut threat, ThreatStatus::Confirmed);
+        count_status_entry(&mut counter, &mut threat, ThreatStatus::UnderInvestigation);
+
+        assert_eq!(counter.total_false_positive, 1);
+        assert_eq!(counter.total_confirmed, 1);
+    }
+
+    #[test]
+    fn consensus_severity_averages_votes() {
+        let mut threat = threat(vec![]);
+        threat.severity_sum += 80 + 100;
+        threat.severity_votes += 2;
+
+        assert_eq!(consensus_severity(&threat), 80);
+    }
+
+    fn contribution(amount: u64) -> BountyContribution {
+        BountyContribution {
+            threat_id: 1,
+            funder: Pubkey::new_unique(),
+            amount,
+            bump: 0,
+        }
+    }
+
+    fn error_code(err: Error) -> u32 {
+        match err {
+            Error::AnchorError(e) => e.error_code_number,
+            Error::ProgramError(e) => panic!("unexpected program error: {e}"),
+        }
+    }
+
+    /// Writable, unsigned account info over caller-owned storage
+    fn info<'a>(
+        key: &'a Pubkey,
+        lamports: &'a mut u64,
+        data: &'a mut [u8],
+        owner: &'a Pubkey,
+    ) -> AccountInfo<'a> {
+        AccountInfo::new(key, false, true, lamports, data, owner, false, 0)
+    }
+
+    #[test]
+    fn funding_tracks_pool_and_contribution() {
+        let (mut threat, mut record) = (threat(vec![]), contribution(0));
+
+        record_bounty_contribution(&mut threat, &mut record, 300).unwrap();
+        record_bounty_contribution(&mut threat, &mut record, 200).unwrap();
+
+        assert_eq!(threat.bounty_amount, 500);
+        assert_eq!(record.amount, 500);
+        assert!(record_bounty_contribution(&mut threat, &mut record, u64::MAX).is_err());
+    }
+
+    #[test]
+    fn claim_pays_detector_remainder_and_confirmers() {
+        let confirmers = keys(2);
+        let mut threat = threat(confirmers.clone());
+        threat.bounty_amount = 1_000;
+
+        let (threat_key, system) = (Pubkey::new_unique(), Pubkey::default());
+        // Bounty on top of the PDA's rent
+        let mut threat_lamports = 1_000 + 5_000;
+        let threat_info = info(&threat_key, &mut threat_lamports, &mut [], &crate::ID);
+        let recipients = [threat.detected_by, confirmers[0], confirmers[1]];
+        let mut lamports = [0u64; 3];
+        let infos: Vec<AccountInfo> = recipients
+            .iter()
+            .zip(lamports.iter_mut())
+            .map(|(key, lamports)| info(key, lamports, &mut [], &system))
+            .collect();
+
+        let (paid, share) = pay_bounty(&mut threat, &threat_info, &infos).unwrap();
+
+        assert_eq!(paid, recipients.to_vec());
+        assert_eq!(share, 333);
+        let balances: Vec<u64> = infos.iter().map(|info| info.lamports()).collect();
+        assert_eq!(balances, vec![334, 333, 333]);
+        assert_eq!(threat_info.lamports(), 5_000);
+        assert!(threat.bounty_claimed);
+        assert_eq!(threat.bounty_amount, 0);
+    }
+
+    #[test]
+    fn claim_rejects_recipients_out_of_order() {
+        let confirmers = keys(1);
+        let mut threat = threat(confirmers.clone());
+        threat.bounty_amount = 10;
+        let detector = threat.detected_by;
+
+        let (threat_key, system) = (Pubkey::new_unique(), Pubkey::default());
+        let mut threat_lamports = 10;
+        let threat_info = info(&threat_key, &mut threat_lamports, &mut [], &crate::ID);
+        let (mut a, mut b) = (0, 0);
+        // Confirmer passed where the detector belongs
+        let infos = vec![
+            info(&confirmers[0], &mut a, &mut [], &system),
+            info(&detector, &mut b, &mut [], &system),
+        ];
+
+        let err = pay_bounty(&mut threat, &threat_info, &infos).unwrap_err();
+
+        assert_eq!(error_code(err), u32::from(ErrorCode::MissingBountyRecipients));
+        assert_eq!(threat_info.lamports(), 10);
+    }
+
+    #[test]
+    fn refund_only_from_dismissed_or_merged_threats() {
+        let mut threat = threat(vec![]);
+        threat.bounty_amount = 700;
+        let mut record = contribution(400);
+        let funder = record.funder;
+
+        let (threat_key, system) = (Pubkey::new_unique(), Pubkey::default());
+        let (mut threat_lamports, mut funder_lamports) = (700, 0);
+        let threat_info = info(&threat_key, &mut threat_lamports, &mut [], &crate::ID);
+        let funder_info = info(&funder, &mut funder_lamports, &mut [], &system);
+        let refund = |threat: &mut Threat, record: &mut BountyContribution| {
+            refund_contribution(threat, record, &threat_info, &funder_info)
+        };
+
+        let err = refund(&mut threat, &mut record).unwrap_err();
+        assert_eq!(error_code(err), u32::from(ErrorCode::BountyNotRefundable));
+
+        threat.status = ThreatStatus::Merged;
+        assert_eq!(refund(&mut threat, &mut record).unwrap(), 400);
+        assert_eq!(threat.bounty_amount, 300);
+        assert_eq!(record.amount, 0);
+
+        // A second refund has nothing left to return
+        let err = refund(&mut threat, &mut record).unwrap_err();
+        assert_eq!(error_code(err), u32::from(ErrorCode::NoBounty));
+
+        assert_eq!(threat_info.lamports(), 300);
+        assert_eq!(funder_info.lamports(), 400);
+    }
+
+    #[test]
+    fn live_aggregates_follow_status_and_severity() {
+        let (mut counter, mut threat) = (counter(), threat(vec![]));
+        counter.active_threats = 1;
+        counter.active_severity_sum = 60;
+
+        set_threat_status(&mut counter, &mut threat, ThreatStatus::Confirmed);
+        set_threat_severity(&mut counter, &mut threat, 75);
+        assert_eq!((counter.active_threats, counter.active_severity_sum), (1, 75));
+
+        set_threat_status(&mut counter, &mut threat, ThreatStatus::Neutralized);
+        assert_eq!((counter.active_threats, counter.active_severity_sum), (0, 0));
+
+        // Severity changes off the live set don't touch the aggregates
+        set_threat_severity(&mut counter, &mut threat, 10);
+        assert_eq!(counter.active_severity_sum, 0);
+    }
+
+    #[test]
+    fn aged_and_merged_threats_leave_live_aggregates() {
+        let (mut counter, mut first, mut second) = (counter(), threat(vec![]), threat(vec![]));
+        counter.active_threats = 2;
+        counter.active_severity_sum = 120;
+
+        set_threat_status(&mut counter, &mut first, ThreatStatus::UnderInvestigation);
+        set_threat_status(&mut counter, &mut second, ThreatStatus::Merged);
+
+        assert_eq!((counter.active_threats, counter.active_severity_sum), (0, 0));
+    }
+
+    #[test]
+    fn threat_counter_layout_matches_coordinator_offsets() {
+        let mut counter = counter();
+        counter.active_threats = 0x0102_0304_0506_0708;
+        counter.active_severity_sum = 0x1112_1314_1516_1718;
+
+        let mut data = vec![];
+        counter.try_serialize(&mut data).unwrap();
+        let read_u64 = |offset: usize| {
+            u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+        };
+
+        assert!(data.len() >= agent_coordinator::THREAT_COUNTER_MIN_LEN);
+        assert_eq!(read_u64(8), counter.count);
+        assert_eq!(
+            read_u64(agent_coordinator::THREAT_COUNTER_ACTIVE_THREATS_OFFSET),
+            counter.active_threats
+        );
+        assert_eq!(
+            read_u64(agent_coordinator::THREAT_COUNTER_ACTIVE_SEVERITY_OFFSET),
+            counter.active_severity_sum
+        );
+    }
+
+    fn swarm_registry(paused: bool) -> Vec<u8> {
+        let swarm = SwarmRegistry {
+            authority: Pubkey::new_unique(),
+            total_agents: 0,
+            type_counts: [0; 10],
+            active_coordinations: 0,
+            active_low: 0,
+            active_medium: 0,
+            active_high: 0,
+            active_critical: 0,
+            total_response_latency: 0,
+            executed_count: 0,
+            total_coordinations: 7,
+            capability_prerequisites: [0; 10],
+            pending_authority: None,
+            paused,
+            forfeited_bonds: 0,
+            bump: 0,
+        };
+        let mut data = vec![];
+        swarm.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn auto_coordination_takes_next_id_unless_paused() {
+        let key = Pubkey::new_unique();
+        let (mut live, mut paused) = (swarm_registry(false), swarm_registry(true));
+        let (mut a, mut b) = (0, 0);
+
+        let live_info = info(&key, &mut a, &mut live, &agent_coordinator::ID);
+        assert_eq!(next_auto_coordination_id(&live_info).unwrap(), Some(7));
+        let paused_info = info(&key, &mut b, &mut paused, &agent_coordinator::ID);
+        assert_eq!(next_auto_coordination_id(&paused_info).unwrap(), None);
+    }
+
+    #[test]
+    fn auto_coordination_rejects_foreign_swarm_registry() {
+        let (key, owner) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (mut data, mut lamports) = (swarm_registry(true), 0);
+
+        let foreign = info(&key, &mut lamports, &mut data, &owner);
+        assert!(next_auto_coordination_id(&foreign).is_err());
+    }
+
+    #[test]
+    fn false_positive_voters_are_capped() {
+        let mut threat = threat(vec![]);
+        for voter in keys(MAX_FALSE_POSITIVE_VOTERS) {
+            record_false_positive_vote(&mut threat, voter).unwrap();
+        }
+
+        let err = record_false_positive_vote(&mut threat, Pubkey::new_unique()).unwrap_err();
+        assert_eq!(error_code(err), u32::from(ErrorCode::TooManyFalsePositiveVoters));
+        assert_eq!(threat.false_positive_votes as usize, MAX_FALSE_POSITIVE_VOTERS);
+    }
 }